@@ -5,6 +5,7 @@
 #[derive(Clone, Copy, Default)]
 #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
 #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Address {
     /// Default I2C address.
     #[default]
@@ -0,0 +1,365 @@
+//! Multi-sensor aggregation and voting.
+//!
+//! Redundant `BMP180` devices are fused into a single reading.
+//! Outliers are rejected using median voting and the remaining readings are averaged.
+
+use duplicate::duplicate_item;
+
+/// Fused reading of `N` devices.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct FusedReading<const N: usize> {
+    /// Combined temperature in `0.1 C`.
+    pub temperature: i32,
+    /// Combined pressure in `Pa`.
+    pub pressure: i32,
+    /// Estimated uncertainty of the combined temperature in `0.1 C`.
+    pub temperature_uncertainty: f32,
+    /// Estimated uncertainty of the combined pressure in `Pa`.
+    pub pressure_uncertainty: f32,
+    /// Devices whose temperature or pressure disagreed with the median and were rejected.
+    pub disagreeing: [bool; N],
+    /// Devices that failed to update.
+    pub failed: [bool; N],
+}
+
+/// Result of voting on a single quantity.
+struct Vote<const N: usize> {
+    /// Mean of the accepted values.
+    value: i32,
+    /// Estimated uncertainty of the mean.
+    uncertainty: f32,
+    /// Values that were rejected.
+    rejected: [bool; N],
+}
+
+/// Vote on the values of the devices that did not fail.
+///
+/// Values further than `tolerance` from the median are rejected, the remaining values are averaged.
+/// The uncertainty is the standard error of the mean, or `tolerance` if a single value remains.
+///
+/// Returns `None` if every device failed.
+fn vote<const N: usize>(values: &[i32; N], failed: &[bool; N], tolerance: i32) -> Option<Vote<N>> {
+    let mut sorted = [0i32; N];
+    let mut len = 0;
+
+    for (value, _) in values.iter().zip(failed).filter(|(_, failed)| !**failed) {
+        sorted[len] = *value;
+        len += 1;
+    }
+
+    if len == 0 {
+        return None;
+    }
+
+    let sorted = &mut sorted[..len];
+
+    sorted.sort_unstable();
+
+    let median = if len % 2 == 1 {
+        sorted[len / 2] as i64
+    } else {
+        (sorted[len / 2 - 1] as i64 + sorted[len / 2] as i64) / 2
+    };
+
+    let mut rejected = [false; N];
+    let mut sum = 0i64;
+    let mut count = 0i64;
+
+    for ((value, failed), rejected) in values.iter().zip(failed).zip(rejected.iter_mut()) {
+        if *failed {
+            continue;
+        }
+
+        if (*value as i64 - median).abs() > tolerance as i64 {
+            *rejected = true;
+
+            continue;
+        }
+
+        sum += *value as i64;
+        count += 1;
+    }
+
+    // The median itself is always within the tolerance, unless it is the mean of two distant values.
+    if count == 0 {
+        return Some(Vote {
+            value: median as i32,
+            uncertainty: tolerance as f32,
+            rejected,
+        });
+    }
+
+    let mean = sum as f32 / count as f32;
+
+    let uncertainty = if count < 2 {
+        tolerance as f32
+    } else {
+        let variance = values
+            .iter()
+            .zip(failed)
+            .zip(rejected.iter())
+            .filter(|((_, failed), rejected)| !**failed && !**rejected)
+            .map(|((value, _), _)| {
+                let delta = *value as f32 - mean;
+
+                delta * delta
+            })
+            .sum::<f32>()
+            / (count - 1) as f32;
+
+        libm::sqrtf(variance / count as f32)
+    };
+
+    Some(Vote {
+        value: libm::roundf(mean) as i32,
+        uncertainty,
+        rejected,
+    })
+}
+
+#[duplicate_item(
+    feature_        module        async     await               i2c_trait                       delay_trait;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs];
+)]
+pub mod module {
+    //! Multi-sensor aggregation.
+
+    #[cfg(feature=feature_)]
+    mod inner {
+        use crate::{
+            device::{module::BMP180, Identity},
+            error::BMP180Error,
+        };
+
+        use super::super::{vote, FusedReading};
+
+        /// Aggregator over `N` redundant `BMP180` devices.
+        pub struct Aggregator<I2C, DELAY, const N: usize> {
+            /// Devices.
+            devices: [BMP180<I2C, DELAY>; N],
+            /// Maximum deviation from the median temperature in `0.1 C`.
+            temperature_tolerance: i32,
+            /// Maximum deviation from the median pressure in `Pa`.
+            pressure_tolerance: i32,
+        }
+
+        impl<I2C, DELAY, const N: usize> Aggregator<I2C, DELAY, N>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+        {
+            /// Create a new aggregator.
+            ///
+            /// Default tolerances are `2 C` and `200 Pa`, twice the absolute accuracy of the `BMP180`.
+            pub fn new(devices: [BMP180<I2C, DELAY>; N]) -> Self {
+                Self {
+                    devices,
+                    temperature_tolerance: 20,
+                    pressure_tolerance: 200,
+                }
+            }
+
+            /// Set the maximum deviation from the median temperature in `0.1 C`.
+            pub fn temperature_tolerance(mut self, tolerance: i32) -> Self {
+                self.temperature_tolerance = tolerance;
+                self
+            }
+
+            /// Set the maximum deviation from the median pressure in `Pa`.
+            pub fn pressure_tolerance(mut self, tolerance: i32) -> Self {
+                self.pressure_tolerance = tolerance;
+                self
+            }
+
+            /// Devices.
+            pub fn devices(&self) -> &[BMP180<I2C, DELAY>; N] {
+                &self.devices
+            }
+
+            /// Mutable devices.
+            pub fn devices_mut(&mut self) -> &mut [BMP180<I2C, DELAY>; N] {
+                &mut self.devices
+            }
+
+            /// Release the devices.
+            pub fn into_devices(self) -> [BMP180<I2C, DELAY>; N] {
+                self.devices
+            }
+
+            /// Update all devices and fuse their readings.
+            ///
+            /// Devices failing to update are excluded.
+            /// If every device fails, the error of the first device is returned.
+            pub async fn update(&mut self) -> Result<FusedReading<N>, BMP180Error<I2C::Error>> {
+                let mut failed = [false; N];
+                let mut temperatures = [0i32; N];
+                let mut pressures = [0i32; N];
+                let mut first_error = None;
+
+                for (index, device) in self.devices.iter_mut().enumerate() {
+                    match device.update().await {
+                        Ok(()) => {
+                            temperatures[index] = device.temperature();
+                            pressures[index] = device.pressure();
+                        }
+                        Err(error) => {
+                            failed[index] = true;
+
+                            if first_error.is_none() {
+                                first_error = Some(error);
+                            }
+                        }
+                    }
+                }
+
+                let temperature = vote(&temperatures, &failed, self.temperature_tolerance);
+                let pressure = vote(&pressures, &failed, self.pressure_tolerance);
+
+                let (Some(temperature), Some(pressure)) = (temperature, pressure) else {
+                    return match first_error {
+                        Some(error) => Err(error),
+                        // Only reachable with `N == 0`.
+                        None => Ok(FusedReading {
+                            temperature: 0,
+                            pressure: 0,
+                            temperature_uncertainty: 0.0,
+                            pressure_uncertainty: 0.0,
+                            disagreeing: [false; N],
+                            failed,
+                        }),
+                    };
+                };
+
+                let mut disagreeing = [false; N];
+
+                for (index, disagreeing) in disagreeing.iter_mut().enumerate() {
+                    *disagreeing = temperature.rejected[index] || pressure.rejected[index];
+                }
+
+                Ok(FusedReading {
+                    temperature: temperature.value,
+                    pressure: pressure.value,
+                    temperature_uncertainty: temperature.uncertainty,
+                    pressure_uncertainty: pressure.uncertainty,
+                    disagreeing,
+                    failed,
+                })
+            }
+        }
+    }
+
+    #[cfg(feature=feature_)]
+    pub use inner::*;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vote_odd_count() {
+        let vote = vote(&[103, 100, 101], &[false; 3], 5).unwrap();
+
+        assert_eq!(vote.value, 101);
+        assert_eq!(vote.rejected, [false; 3]);
+    }
+
+    #[test]
+    fn vote_even_count() {
+        // The median is the mean of `102` and `104`, `100` and `106` are within the tolerance of `103`.
+        let vote = vote(&[106, 100, 104, 102], &[false; 4], 3).unwrap();
+
+        assert_eq!(vote.value, 103);
+        assert_eq!(vote.rejected, [false; 4]);
+
+        let vote = super::vote(&[106, 100, 104, 102], &[false; 4], 2).unwrap();
+
+        assert_eq!(vote.value, 103);
+        assert_eq!(vote.rejected, [true, true, false, false]);
+    }
+
+    #[test]
+    fn vote_rejects_outlier() {
+        let vote = vote(&[100, 150, 101], &[false; 3], 10).unwrap();
+
+        assert_eq!(vote.value, 101);
+        assert_eq!(vote.rejected, [false, true, false]);
+        assert_eq!(vote.uncertainty, 0.5);
+    }
+
+    #[test]
+    fn vote_two_distant_values() {
+        let vote = vote(&[100, 200], &[false; 2], 10).unwrap();
+
+        assert_eq!(vote.value, 150);
+        assert_eq!(vote.rejected, [true; 2]);
+        assert_eq!(vote.uncertainty, 10.0);
+    }
+
+    #[test]
+    fn vote_excludes_failed() {
+        let vote = vote(&[100, 0, 102], &[false, true, false], 10).unwrap();
+
+        assert_eq!(vote.value, 101);
+        assert_eq!(vote.rejected, [false; 3]);
+
+        assert!(super::vote(&[100, 102], &[true; 2], 10).is_none());
+    }
+
+    #[test]
+    fn vote_standard_error() {
+        // Sample variance `4`, standard error `sqrt(4 / 3)`.
+        let vote = vote(&[100, 102, 104], &[false; 3], 10).unwrap();
+
+        assert_eq!(vote.value, 102);
+        assert!((vote.uncertainty - 1.154_700_5).abs() < 1e-6);
+
+        // A single value has the tolerance as uncertainty.
+        let vote = super::vote(&[100, 102], &[false, true], 10).unwrap();
+
+        assert_eq!(vote.uncertainty, 10.0);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn aggregator_rejects_failed_and_disagreeing() {
+        use embassy_futures::block_on;
+
+        use crate::{aggregate::asynch::Aggregator, asynch::UninitBMP180, mock::mock, RetryPolicy};
+
+        let devices = [23843, 23843, 30000, 23843].map(|raw_pressure| {
+            let (i2c, delay, state) = mock();
+
+            state.borrow_mut().raw_pressure = raw_pressure;
+
+            let bmp180 = block_on(
+                UninitBMP180::builder(i2c, delay)
+                    .retry_policy(RetryPolicy::new(0))
+                    .build()
+                    .initialize(),
+            )
+            .unwrap();
+
+            (bmp180, state)
+        });
+
+        let [(first, _), (second, _), (third, _), (fourth, state)] = devices;
+
+        state.borrow_mut().failures = usize::MAX;
+
+        let mut aggregator = Aggregator::new([first, second, third, fourth]);
+
+        let reading = block_on(aggregator.update()).unwrap();
+
+        assert_eq!(reading.temperature, 150);
+        assert_eq!(reading.pressure, aggregator.devices()[0].pressure());
+        assert_ne!(reading.pressure, aggregator.devices()[2].pressure());
+        assert_eq!(reading.temperature_uncertainty, 0.0);
+        assert_eq!(reading.pressure_uncertainty, 0.0);
+        assert_eq!(reading.disagreeing, [false, false, true, false]);
+        assert_eq!(reading.failed, [false, false, false, true]);
+    }
+}
@@ -0,0 +1,179 @@
+//! Command line arguments.
+
+use bmp180_embedded_hal::{Address, Mode};
+
+/// Usage text.
+pub const USAGE: &str = "\
+Usage: bmp180 [OPTIONS] <COMMAND>
+
+Commands:
+  probe         Read and validate the chip ID
+  calibration   Dump the calibration data
+  read          Take readings
+
+Options:
+  --device <PATH>      I2C device [default: /dev/i2c-1]
+  --address <ADDR>     I2C address, decimal or hex with `0x` prefix [default: 0x77]
+  --mock               Use an in-process simulated device instead of the I2C device
+  --format <FORMAT>    Output format: text, csv or json [default: text]
+  -h, --help           Print help
+
+Read options:
+  --mode <MODE>        Operating mode: ultra-low-power, standard, high-resolution
+                       or ultra-high-resolution [default: standard]
+  --count <N>          Number of readings [default: 1]
+  --continuous         Take readings until interrupted
+  --interval <MS>      Interval between readings in milliseconds [default: 1000]
+  --qnh <HPA>          Sea level pressure in hPa, prints the altitude
+";
+
+/// Output format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable text.
+    #[default]
+    Text,
+    /// Comma separated values with a header line.
+    Csv,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Command to run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Read and validate the chip ID.
+    Probe,
+    /// Dump the calibration data.
+    Calibration,
+    /// Take readings.
+    Read,
+}
+
+/// Parsed command line arguments.
+#[derive(Clone, Debug)]
+pub struct Args {
+    /// Command to run.
+    pub command: Command,
+    /// I2C device path.
+    pub device: String,
+    /// I2C address.
+    pub address: Address,
+    /// Use the simulated device.
+    pub mock: bool,
+    /// Output format.
+    pub format: Format,
+    /// Operating mode.
+    pub mode: Mode,
+    /// Number of readings, `None` for continuous readings.
+    pub count: Option<u64>,
+    /// Interval between readings in milliseconds.
+    pub interval_ms: u64,
+    /// Sea level pressure in `Pa`.
+    pub qnh: Option<f32>,
+}
+
+impl Args {
+    /// Default arguments for the given command.
+    fn new(command: Command) -> Self {
+        Self {
+            command,
+            device: String::from("/dev/i2c-1"),
+            address: Address::default(),
+            mock: false,
+            format: Format::default(),
+            mode: Mode::default(),
+            count: Some(1),
+            interval_ms: 1000,
+            qnh: None,
+        }
+    }
+
+    /// Parse the arguments, without the program name.
+    ///
+    /// Returns `None` if help was requested.
+    pub fn parse<I>(args: I) -> Result<Option<Self>, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Args::new(Command::Read);
+        let mut command = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{name}`"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--device" => parsed.device = value(&arg)?,
+                "--address" => parsed.address = parse_address(&value(&arg)?)?,
+                "--mock" => parsed.mock = true,
+                "--format" => parsed.format = parse_format(&value(&arg)?)?,
+                "--mode" => parsed.mode = parse_mode(&value(&arg)?)?,
+                "--count" => parsed.count = Some(parse_number(&arg, &value(&arg)?)?),
+                "--continuous" => parsed.count = None,
+                "--interval" => parsed.interval_ms = parse_number(&arg, &value(&arg)?)?,
+                "--qnh" => {
+                    let hpa: f32 = parse_number(&arg, &value(&arg)?)?;
+
+                    parsed.qnh = Some(hpa * 100.0);
+                }
+                "probe" | "calibration" | "read" if command.is_none() => {
+                    command = Some(match arg.as_str() {
+                        "probe" => Command::Probe,
+                        "calibration" => Command::Calibration,
+                        _ => Command::Read,
+                    });
+                }
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+
+        parsed.command = command.ok_or_else(|| String::from("missing command"))?;
+
+        Ok(Some(parsed))
+    }
+}
+
+/// Parse an I2C address, decimal or hex with `0x` prefix.
+fn parse_address(value: &str) -> Result<Address, String> {
+    let address = match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+
+    address
+        .map(Address::from)
+        .map_err(|_| format!("invalid address `{value}`"))
+}
+
+/// Parse an output format.
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "csv" => Ok(Format::Csv),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("invalid format `{value}`")),
+    }
+}
+
+/// Parse an operating mode.
+fn parse_mode(value: &str) -> Result<Mode, String> {
+    match value {
+        "ultra-low-power" => Ok(Mode::UltraLowPower),
+        "standard" => Ok(Mode::Standard),
+        "high-resolution" => Ok(Mode::HighResolution),
+        "ultra-high-resolution" => Ok(Mode::UltraHighResolution),
+        _ => Err(format!("invalid mode `{value}`")),
+    }
+}
+
+/// Parse a number.
+fn parse_number<T: core::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{name}`"))
+}
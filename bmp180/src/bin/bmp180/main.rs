@@ -0,0 +1,462 @@
+//! Command line tool to interface with a `BMP180` device on Linux.
+//!
+//! Only available when the `linux-cli` feature is enabled.
+
+mod args;
+mod mock;
+
+use std::{fmt, io::Write, process::ExitCode, time::Duration};
+
+use bmp180_embedded_hal::{blocking::UninitBMP180, BMP180Error, Calibration, Id, Mode, Register};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use linux_embedded_hal::{Delay, I2cdev};
+
+use crate::{
+    args::{Args, Command, Format, USAGE},
+    mock::MockI2C,
+};
+
+/// Command line tool error.
+enum Error<E> {
+    /// Output error.
+    Io(std::io::Error),
+    /// I2C error.
+    I2C(E),
+    /// Device error.
+    Device(BMP180Error<E>),
+}
+
+impl<E> From<std::io::Error> for Error<E> {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "output error: {error}"),
+            Error::I2C(error) => write!(f, "I2C error: {error:?}"),
+            Error::Device(error) => write!(f, "device error: {error}"),
+        }
+    }
+}
+
+/// Name of the operating mode as accepted by `--mode`.
+fn mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::UltraLowPower => "ultra-low-power",
+        Mode::Standard => "standard",
+        Mode::HighResolution => "high-resolution",
+        Mode::UltraHighResolution => "ultra-high-resolution",
+    }
+}
+
+/// Read and validate the chip ID.
+fn probe<I2C, W>(args: &Args, mut i2c: I2C, out: &mut W) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c,
+    W: Write,
+{
+    let mut data = [0u8; 1];
+
+    i2c.write_read(args.address.into(), &[Register::ChipId as u8], &mut data)
+        .map_err(Error::I2C)?;
+
+    let id = data[0];
+    let valid = Id::is_valid(id);
+
+    match args.format {
+        Format::Text => writeln!(out, "chip id: {id:#04x} ({})", valid_name(valid))?,
+        Format::Csv => writeln!(out, "chip_id,valid\n{id:#04x},{valid}")?,
+        Format::Json => writeln!(out, "{{\"chip_id\":\"{id:#04x}\",\"valid\":{valid}}}")?,
+    }
+
+    Ok(())
+}
+
+/// Human readable chip ID validity.
+fn valid_name(valid: bool) -> &'static str {
+    if valid {
+        "valid"
+    } else {
+        "invalid"
+    }
+}
+
+/// Calibration coefficient names and values.
+fn coefficients(calibration: &Calibration) -> [(&'static str, i32); 11] {
+    [
+        ("ac1", calibration.ac1 as i32),
+        ("ac2", calibration.ac2 as i32),
+        ("ac3", calibration.ac3 as i32),
+        ("ac4", calibration.ac4 as i32),
+        ("ac5", calibration.ac5 as i32),
+        ("ac6", calibration.ac6 as i32),
+        ("b1", calibration.b1 as i32),
+        ("b2", calibration.b2 as i32),
+        ("mb", calibration.mb as i32),
+        ("mc", calibration.mc as i32),
+        ("md", calibration.md as i32),
+    ]
+}
+
+/// Dump the calibration data.
+fn calibration<I2C, DELAY, W>(
+    args: &Args,
+    i2c: I2C,
+    delay: DELAY,
+    out: &mut W,
+) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+    W: Write,
+{
+    let bmp180 = UninitBMP180::builder(i2c, delay)
+        .addr(args.address)
+        .build()
+        .initialize()
+        .map_err(Error::Device)?;
+
+    let coefficients = coefficients(bmp180.calibration());
+
+    match args.format {
+        Format::Text => {
+            for (name, value) in coefficients {
+                writeln!(out, "{name}: {value}")?;
+            }
+        }
+        Format::Csv => {
+            let names: Vec<_> = coefficients.iter().map(|(name, _)| *name).collect();
+            let values: Vec<_> = coefficients
+                .iter()
+                .map(|(_, value)| value.to_string())
+                .collect();
+
+            writeln!(out, "{}\n{}", names.join(","), values.join(","))?;
+        }
+        Format::Json => {
+            let fields: Vec<_> = coefficients
+                .iter()
+                .map(|(name, value)| format!("\"{name}\":{value}"))
+                .collect();
+
+            writeln!(out, "{{{}}}", fields.join(","))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Take readings.
+fn read<I2C, DELAY, W>(
+    args: &Args,
+    i2c: I2C,
+    delay: DELAY,
+    out: &mut W,
+) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+    W: Write,
+{
+    let mut bmp180 = UninitBMP180::builder(i2c, delay)
+        .addr(args.address)
+        .mode(args.mode)
+        .build()
+        .initialize()
+        .map_err(Error::Device)?;
+
+    let mode = mode_name(args.mode);
+
+    if args.format == Format::Csv {
+        writeln!(out, "mode,temperature_c,pressure_pa,altitude_m")?;
+    }
+
+    let mut taken = 0;
+
+    while args.count.is_none_or(|count| taken < count) {
+        if taken > 0 {
+            std::thread::sleep(Duration::from_millis(args.interval_ms));
+        }
+
+        bmp180.update().map_err(Error::Device)?;
+
+        let temperature = bmp180.typed_temperature();
+        let pressure = bmp180.pressure();
+        let altitude = args.qnh.map(|qnh| bmp180.altitude(qnh));
+
+        match args.format {
+            Format::Text => {
+                write!(
+                    out,
+                    "mode: {mode}, temperature: {temperature}, pressure: {pressure} Pa"
+                )?;
+
+                if let Some(altitude) = altitude {
+                    write!(out, ", altitude: {altitude:.1} m")?;
+                }
+
+                writeln!(out)?;
+            }
+            Format::Csv => {
+                let altitude = altitude
+                    .map(|altitude| format!("{altitude:.1}"))
+                    .unwrap_or_default();
+
+                writeln!(
+                    out,
+                    "{mode},{:.1},{pressure},{altitude}",
+                    bmp180.temperature_celsius()
+                )?;
+            }
+            Format::Json => {
+                let altitude = altitude
+                    .map(|altitude| format!("{altitude:.1}"))
+                    .unwrap_or_else(|| String::from("null"));
+
+                writeln!(
+                    out,
+                    "{{\"mode\":\"{mode}\",\"temperature_c\":{:.1},\"pressure_pa\":{pressure},\"altitude_m\":{altitude}}}",
+                    bmp180.temperature_celsius()
+                )?;
+            }
+        }
+
+        out.flush()?;
+
+        taken += 1;
+    }
+
+    Ok(())
+}
+
+/// Run the command on the given bus.
+fn run<I2C, DELAY, W>(
+    args: &Args,
+    i2c: I2C,
+    delay: DELAY,
+    out: &mut W,
+) -> Result<(), Error<I2C::Error>>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+    W: Write,
+{
+    match args.command {
+        Command::Probe => probe(args, i2c, out),
+        Command::Calibration => calibration(args, i2c, delay, out),
+        Command::Read => read(args, i2c, delay, out),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut out = std::io::stdout().lock();
+
+    let result = if args.mock {
+        run(&args, MockI2C::default(), Delay, &mut out).map_err(|error| error.to_string())
+    } else {
+        match I2cdev::new(&args.device) {
+            Ok(i2c) => run(&args, i2c, Delay, &mut out).map_err(|error| error.to_string()),
+            Err(error) => Err(format!("could not open `{}`: {error}", args.device)),
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::i2c::{ErrorType, Operation};
+
+    use super::*;
+
+    /// Delay that does nothing.
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _: u32) {}
+    }
+
+    /// Bus answering every read with `0xFF`, like a missing device with pull-ups.
+    struct FloatingI2C;
+
+    impl ErrorType for FloatingI2C {
+        type Error = Infallible;
+    }
+
+    impl I2c for FloatingI2C {
+        fn transaction(
+            &mut self,
+            _address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Read(read) = operation {
+                    read.fill(0xFF);
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Parse `args` and run the command against the simulated device.
+    fn run_mock(args: &str) -> String {
+        let args = Args::parse(args.split_whitespace().map(String::from))
+            .unwrap()
+            .unwrap();
+
+        let mut out = Vec::new();
+
+        if run(&args, MockI2C::default(), NoDelay, &mut out).is_err() {
+            panic!("command failed");
+        }
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parse_defaults() {
+        let args = Args::parse([String::from("read")]).unwrap().unwrap();
+
+        assert_eq!(args.command, Command::Read);
+        assert_eq!(args.device, "/dev/i2c-1");
+        assert_eq!(u8::from(args.address), 0x77);
+        assert!(!args.mock);
+        assert_eq!(args.format, Format::Text);
+        assert_eq!(args.mode, Mode::Standard);
+        assert_eq!(args.count, Some(1));
+        assert_eq!(args.interval_ms, 1000);
+        assert_eq!(args.qnh, None);
+    }
+
+    #[test]
+    fn parse_options() {
+        let args = Args::parse(
+            "--address 0x76 --format json read --mode ultra-high-resolution --continuous --interval 10 --qnh 1013.25"
+                .split_whitespace()
+                .map(String::from),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(u8::from(args.address), 0x76);
+        assert_eq!(args.format, Format::Json);
+        assert_eq!(args.mode, Mode::UltraHighResolution);
+        assert_eq!(args.count, None);
+        assert_eq!(args.interval_ms, 10);
+        assert_eq!(args.qnh, Some(101325.0));
+    }
+
+    #[test]
+    fn parse_help_and_errors() {
+        assert!(Args::parse([String::from("--help")]).unwrap().is_none());
+
+        for (args, error) in [
+            ("", "missing command"),
+            ("read --format xml", "invalid format `xml`"),
+            ("read --mode fast", "invalid mode `fast`"),
+            ("read --count", "missing value for `--count`"),
+            ("read --address 0xZZ", "invalid address `0xZZ`"),
+            ("read probe", "unexpected argument `probe`"),
+        ] {
+            assert_eq!(
+                Args::parse(args.split_whitespace().map(String::from)).unwrap_err(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn probe_outputs() {
+        assert_eq!(run_mock("--mock probe"), "chip id: 0x55 (valid)\n");
+        assert_eq!(
+            run_mock("--mock --format csv probe"),
+            "chip_id,valid\n0x55,true\n"
+        );
+        assert_eq!(
+            run_mock("--mock --format json probe"),
+            "{\"chip_id\":\"0x55\",\"valid\":true}\n"
+        );
+    }
+
+    #[test]
+    fn probe_invalid_id() {
+        let args = Args::parse([String::from("probe")]).unwrap().unwrap();
+        let mut out = Vec::new();
+
+        assert!(run(&args, FloatingI2C, NoDelay, &mut out).is_ok());
+        assert_eq!(String::from_utf8(out).unwrap(), "chip id: 0xff (invalid)\n");
+    }
+
+    #[test]
+    fn calibration_outputs() {
+        let text = run_mock("--mock calibration");
+
+        assert!(text.starts_with("ac1: 408\nac2: -72\n"));
+        assert!(text.ends_with("md: 2868\n"));
+
+        assert_eq!(
+            run_mock("--mock --format csv calibration"),
+            "ac1,ac2,ac3,ac4,ac5,ac6,b1,b2,mb,mc,md\n408,-72,-14383,32741,32757,23153,6190,4,-32768,-8711,2868\n"
+        );
+        assert_eq!(
+            run_mock("--mock --format json calibration"),
+            "{\"ac1\":408,\"ac2\":-72,\"ac3\":-14383,\"ac4\":32741,\"ac5\":32757,\"ac6\":23153,\"b1\":6190,\"b2\":4,\"mb\":-32768,\"mc\":-8711,\"md\":2868}\n"
+        );
+    }
+
+    #[test]
+    fn read_outputs() {
+        assert_eq!(
+            run_mock("--mock read --mode ultra-low-power"),
+            "mode: ultra-low-power, temperature: 15.0 °C, pressure: 69964 Pa\n"
+        );
+        assert_eq!(
+            run_mock("--mock --format csv read --mode ultra-low-power --count 2 --interval 0"),
+            "mode,temperature_c,pressure_pa,altitude_m\nultra-low-power,15.0,69964,\nultra-low-power,15.0,69964,\n"
+        );
+        assert_eq!(
+            run_mock("--mock --format json read --mode ultra-low-power"),
+            "{\"mode\":\"ultra-low-power\",\"temperature_c\":15.0,\"pressure_pa\":69964,\"altitude_m\":null}\n"
+        );
+    }
+
+    #[test]
+    fn read_altitude() {
+        // 44330 * (1 - (69964 / 101325) ^ 0.1903)
+        assert_eq!(
+            run_mock("--mock read --mode ultra-low-power --qnh 1013.25"),
+            "mode: ultra-low-power, temperature: 15.0 °C, pressure: 69964 Pa, altitude: 3016.7 m\n"
+        );
+        assert_eq!(
+            run_mock("--mock --format csv read --mode ultra-low-power --qnh 1013.25"),
+            "mode,temperature_c,pressure_pa,altitude_m\nultra-low-power,15.0,69964,3016.7\n"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! In-process simulated `BMP180` device.
+
+use core::convert::Infallible;
+
+use bmp180_embedded_hal::{Calibration, Id, Register};
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+/// Calibration data from the example in the datasheet.
+const CALIBRATION: Calibration = Calibration {
+    ac1: 408,
+    ac2: -72,
+    ac3: -14383,
+    ac4: 32741,
+    ac5: 32757,
+    ac6: 23153,
+    b1: 6190,
+    b2: 4,
+    mb: -32768,
+    mc: -8711,
+    md: 2868,
+};
+
+/// Raw temperature from the example in the datasheet.
+const RAW_TEMPERATURE: u16 = 27898;
+
+/// Raw pressure from the example in the datasheet, in ultra low power mode.
+const RAW_PRESSURE: u32 = 23843;
+
+/// Simulated `BMP180` device answering with the datasheet example values.
+///
+/// The raw pressure is scaled with the requested oversampling setting,
+/// so every mode yields roughly the same pressure.
+#[derive(Debug, Default)]
+pub struct MockI2C {
+    /// Register pointer.
+    register: u8,
+    /// Last command written to the control register.
+    command: u8,
+}
+
+impl ErrorType for MockI2C {
+    type Error = Infallible;
+}
+
+impl MockI2C {
+    /// Fill `read` with the content of the registers starting at the register pointer.
+    fn fill(&self, read: &mut [u8]) {
+        let mut data = [0u8; 22];
+        let calibration = Register::CalibrationAc1 as u8..=Register::CalibrationMd as u8 + 1;
+
+        match self.register {
+            register if register == Register::ChipId as u8 => data[0] = Id::Valid as u8,
+            register if calibration.contains(&register) => {
+                let offset = (register - Register::CalibrationAc1 as u8) as usize;
+                let bytes = CALIBRATION.to_bytes();
+
+                data[..bytes.len() - offset].copy_from_slice(&bytes[offset..]);
+            }
+            register if register == Register::TempPressureData as u8 => {
+                if self.command == Register::ReadTempCmd as u8 {
+                    data[..2].copy_from_slice(&RAW_TEMPERATURE.to_be_bytes());
+                } else {
+                    data[..4].copy_from_slice(&(RAW_PRESSURE << 16).to_be_bytes());
+                }
+            }
+            _ => {}
+        }
+
+        let len = read.len().min(data.len());
+
+        read[..len].copy_from_slice(&data[..len]);
+    }
+}
+
+impl I2c for MockI2C {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(write) => {
+                    if let [register, rest @ ..] = write {
+                        self.register = *register;
+
+                        if *register == Register::Control as u8 {
+                            if let [command, ..] = rest {
+                                self.command = *command;
+                            }
+                        }
+                    }
+                }
+                Operation::Read(read) => self.fill(read),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 //! Calibration data.
 
+use crate::{crc::crc16, error::CalibrationLengthError, register::Register, tri};
+
 /// Calibration data according to the BMP180 datasheet.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
 #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Calibration {
     /// AC1.
     pub ac1: i16,
@@ -32,6 +35,9 @@ pub struct Calibration {
 }
 
 impl Calibration {
+    /// Length of a serialized calibration record.
+    pub const RECORD_LEN: usize = 24;
+
     /// Create a new [`Calibration`] instance from a slice.
     pub fn from_slice(slice: &[u8; 22]) -> Self {
         let ac1 = (slice[0] as i16) << 8 | slice[1] as i16;
@@ -62,4 +68,261 @@ impl Calibration {
             md,
         }
     }
+
+    /// Raw big-endian EEPROM image, the inverse of [`Calibration::from_slice`].
+    pub fn to_bytes(&self) -> [u8; 22] {
+        let mut bytes = [0u8; 22];
+
+        bytes[0..2].copy_from_slice(&self.ac1.to_be_bytes());
+        bytes[2..4].copy_from_slice(&self.ac2.to_be_bytes());
+        bytes[4..6].copy_from_slice(&self.ac3.to_be_bytes());
+        bytes[6..8].copy_from_slice(&self.ac4.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.ac5.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.ac6.to_be_bytes());
+
+        bytes[12..14].copy_from_slice(&self.b1.to_be_bytes());
+        bytes[14..16].copy_from_slice(&self.b2.to_be_bytes());
+
+        bytes[16..18].copy_from_slice(&self.mb.to_be_bytes());
+        bytes[18..20].copy_from_slice(&self.mc.to_be_bytes());
+        bytes[20..22].copy_from_slice(&self.md.to_be_bytes());
+
+        bytes
+    }
+
+    /// Whether the calibration data is plausible.
+    ///
+    /// None of the calibration words has the value `0x0000` or `0xFFFF`,
+    /// which would indicate a failed communication or a damaged EEPROM.
+    pub fn is_valid(&self) -> bool {
+        self.to_bytes()
+            .chunks_exact(2)
+            .all(|word| word != [0x00, 0x00] && word != [0xFF, 0xFF])
+    }
+
+    /// Raw big-endian calibration word stored at the EEPROM `register`.
+    ///
+    /// Returns `None` if `register` is not one of the calibration registers,
+    /// [`Register::CalibrationAc1`] to [`Register::CalibrationMd`].
+    pub fn register_bytes(&self, register: Register) -> Option<[u8; 2]> {
+        let start = Register::CalibrationAc1 as u8;
+        let end = Register::CalibrationMd as u8;
+
+        if !(start..=end).contains(&(register as u8)) {
+            return None;
+        }
+
+        let index = (register as u8 - start) as usize;
+        let bytes = self.to_bytes();
+
+        Some([bytes[index], bytes[index + 1]])
+    }
+
+    /// Serialize into a compact record, the 22 EEPROM bytes followed by a big-endian `CRC-16/CCITT-FALSE`.
+    ///
+    /// Used to persist the calibration data and skip reading the EEPROM on initialization.
+    pub fn to_record(&self) -> [u8; Self::RECORD_LEN] {
+        let mut record = [0u8; Self::RECORD_LEN];
+
+        record[..22].copy_from_slice(&self.to_bytes());
+
+        let crc = crc16(&record[..22]);
+
+        record[22..].copy_from_slice(&crc.to_be_bytes());
+
+        record
+    }
+
+    /// Deserialize from a record created with [`Calibration::to_record`].
+    ///
+    /// Returns `None` if the record is not [`Calibration::RECORD_LEN`] bytes long or the CRC does not match.
+    pub fn from_record(record: &[u8]) -> Option<Self> {
+        let record: &[u8; Self::RECORD_LEN] = match record.try_into() {
+            Ok(record) => record,
+            Err(_) => return None,
+        };
+
+        let crc = u16::from_be_bytes([record[22], record[23]]);
+
+        if crc16(&record[..22]) != crc {
+            return None;
+        }
+
+        let mut data = [0u8; 22];
+
+        data.copy_from_slice(&record[..22]);
+
+        Some(Self::from_slice(&data))
+    }
+
+    /// Store the calibration record at `offset` in `storage`.
+    ///
+    /// Only available when the `embedded-storage` feature is enabled.
+    #[cfg(feature = "embedded-storage")]
+    pub fn store<S>(&self, storage: &mut S, offset: u32) -> Result<(), S::Error>
+    where
+        S: embedded_storage::Storage,
+    {
+        storage.write(offset, &self.to_record())
+    }
+
+    /// Load a calibration record from `offset` in `storage`.
+    ///
+    /// Returns `Ok(None)` if the CRC does not match, e.g. if nothing was stored yet.
+    ///
+    /// Only available when the `embedded-storage` feature is enabled.
+    #[cfg(feature = "embedded-storage")]
+    pub fn load<S>(storage: &mut S, offset: u32) -> Result<Option<Self>, S::Error>
+    where
+        S: embedded_storage::ReadStorage,
+    {
+        let mut record = [0u8; Self::RECORD_LEN];
+
+        tri!(storage.read(offset, &mut record));
+
+        Ok(Self::from_record(&record))
+    }
+}
+
+impl From<&[u8; 22]> for Calibration {
+    fn from(slice: &[u8; 22]) -> Self {
+        Self::from_slice(slice)
+    }
+}
+
+impl From<&Calibration> for [u8; 22] {
+    fn from(calibration: &Calibration) -> Self {
+        calibration.to_bytes()
+    }
+}
+
+impl TryFrom<&[u8]> for Calibration {
+    type Error = CalibrationLengthError;
+
+    /// Create a new [`Calibration`] instance from a slice of exactly 22 bytes.
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let data: &[u8; 22] = tri!(slice
+            .try_into()
+            .map_err(|_| CalibrationLengthError(slice.len())));
+
+        Ok(Self::from_slice(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::*;
+
+    /// Calibration data of the datasheet example.
+    fn calibration() -> Calibration {
+        Calibration {
+            ac1: 408,
+            ac2: -72,
+            ac3: -14383,
+            ac4: 32741,
+            ac5: 32757,
+            ac6: 23153,
+            b1: 6190,
+            b2: 4,
+            mb: -32768,
+            mc: -8711,
+            md: 2868,
+        }
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let record = calibration().to_record();
+
+        assert_eq!(record[..22], calibration().to_bytes());
+        assert!(Calibration::from_record(&record) == Some(calibration()));
+    }
+
+    #[test]
+    fn record_with_corrupted_byte() {
+        let record = calibration().to_record();
+
+        for index in 0..Calibration::RECORD_LEN {
+            let mut corrupted = record;
+
+            corrupted[index] ^= 0x01;
+
+            assert!(Calibration::from_record(&corrupted).is_none(), "{index}");
+        }
+    }
+
+    #[test]
+    fn record_with_wrong_length() {
+        let record = calibration().to_record();
+
+        assert!(Calibration::from_record(&record[..23]).is_none());
+        assert!(Calibration::from_record(&[]).is_none());
+
+        let mut longer = [0u8; Calibration::RECORD_LEN + 1];
+
+        longer[..Calibration::RECORD_LEN].copy_from_slice(&record);
+
+        assert!(Calibration::from_record(&longer).is_none());
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = calibration().to_bytes();
+
+        assert!(Calibration::try_from(&bytes[..]) == Ok(calibration()));
+        assert!(Calibration::from(&bytes) == calibration());
+        assert_eq!(<[u8; 22]>::from(&calibration()), bytes);
+    }
+
+    #[test]
+    fn bytes_with_wrong_length() {
+        let bytes = [0u8; 23];
+
+        for len in [0, 21, 23] {
+            assert!(Calibration::try_from(&bytes[..len]) == Err(CalibrationLengthError(len)));
+        }
+
+        assert_eq!(
+            CalibrationLengthError(21).to_string(),
+            "invalid calibration data length: 21, expected 22 bytes"
+        );
+    }
+
+    #[test]
+    fn register_bytes() {
+        let calibration = calibration();
+        let bytes = calibration.to_bytes();
+
+        let registers = [
+            Register::CalibrationAc1,
+            Register::CalibrationAc2,
+            Register::CalibrationAc3,
+            Register::CalibrationAc4,
+            Register::CalibrationAc5,
+            Register::CalibrationAc6,
+            Register::CalibrationB1,
+            Register::CalibrationB2,
+            Register::CalibrationMb,
+            Register::CalibrationMc,
+            Register::CalibrationMd,
+        ];
+
+        for (register, word) in registers.into_iter().zip(bytes.chunks_exact(2)) {
+            assert_eq!(calibration.register_bytes(register).unwrap(), word);
+        }
+
+        assert_eq!(
+            calibration.register_bytes(Register::CalibrationMd),
+            Some(2868i16.to_be_bytes())
+        );
+        assert_eq!(calibration.register_bytes(Register::ChipId), None);
+        assert_eq!(calibration.register_bytes(Register::Control), None);
+    }
+
+    #[test]
+    fn erased_record() {
+        assert!(Calibration::from_record(&[0xFF; Calibration::RECORD_LEN]).is_none());
+    }
 }
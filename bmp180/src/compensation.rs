@@ -0,0 +1,497 @@
+//! Temperature and pressure compensation according to the BMP180 datasheet.
+//!
+//! Shared by the devices and the offline decoder, so both compute the exact same values.
+
+use crate::{calibration::Calibration, error::ArithmeticError, mode::Mode, tri};
+
+#[cfg(not(feature = "disable-arithmetic-checks"))]
+use crate::error::{ArithmeticReason, Stage};
+
+/// Compute B5 value.
+#[cfg(not(feature = "disable-arithmetic-checks"))]
+fn compute_b5(calibration: &Calibration, raw_temperature: i16) -> Result<i32, ArithmeticError> {
+    let error = |reason| ArithmeticError {
+        stage: Stage::B5,
+        reason,
+        raw_temperature,
+        raw_pressure: None,
+    };
+
+    let rt = raw_temperature as i32;
+    let ac6 = calibration.ac6 as i32;
+    let ac5 = calibration.ac5 as i32;
+    let mc = calibration.mc as i32;
+    let md = calibration.md as i32;
+
+    let x1 = tri!(rt
+        .checked_sub(ac6)
+        .and_then(|x1| x1.checked_mul(ac5))
+        .and_then(|x1| x1.checked_shr(15))
+        .ok_or(error(ArithmeticReason::Overflow)));
+
+    let divisor = tri!(x1.checked_add(md).ok_or(error(ArithmeticReason::Overflow)));
+
+    if divisor == 0 {
+        return Err(error(ArithmeticReason::DivisionByZero));
+    }
+
+    let x2 = tri!(mc
+        .checked_shl(11)
+        .and_then(|x2| x2.checked_div(divisor))
+        .ok_or(error(ArithmeticReason::Overflow)));
+
+    x1.checked_add(x2).ok_or(error(ArithmeticReason::Overflow))
+}
+
+/// Compute true temprature in `0.1 C`.
+#[cfg(not(feature = "disable-arithmetic-checks"))]
+pub(crate) fn compute_temperature(
+    calibration: &Calibration,
+    raw_temperature: i16,
+) -> Result<i32, ArithmeticError> {
+    let b5 = tri!(compute_b5(calibration, raw_temperature));
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("Computing temperature");
+        defmt::debug!("Raw temperature: {}", raw_temperature);
+        defmt::debug!("B5: {}", b5);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("Computing temperature");
+        log::debug!("Raw temperature: {}", raw_temperature);
+        log::debug!("B5: {}", b5);
+    }
+
+    b5.checked_add(8)
+        .and_then(|temperature| temperature.checked_shr(4))
+        .ok_or(ArithmeticError {
+            stage: Stage::Temperature,
+            reason: ArithmeticReason::Overflow,
+            raw_temperature,
+            raw_pressure: None,
+        })
+}
+
+/// Compute true pressure in `Pa`.
+#[cfg(not(feature = "disable-arithmetic-checks"))]
+pub(crate) fn compute_pressure(
+    calibration: &Calibration,
+    mode: Mode,
+    raw_temperature: i16,
+    raw_pressure: i32,
+) -> Result<i32, ArithmeticError> {
+    let error = |stage, reason| ArithmeticError {
+        stage,
+        reason,
+        raw_temperature,
+        raw_pressure: Some(raw_pressure),
+    };
+
+    let overflow = |stage| error(stage, ArithmeticReason::Overflow);
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("Computing pressure");
+        defmt::debug!("Raw temperature: {}", raw_temperature);
+        defmt::debug!("Raw pressure: {}", raw_pressure);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("Computing pressure");
+        log::debug!("Raw temperature: {}", raw_temperature);
+        log::debug!("Raw pressure: {}", raw_pressure);
+    }
+
+    let b2 = calibration.b2 as i32;
+    let ac2 = calibration.ac2 as i32;
+    let ac1 = calibration.ac1 as i32;
+
+    let b5 = tri!(
+        compute_b5(calibration, raw_temperature).map_err(|error| ArithmeticError {
+            raw_pressure: Some(raw_pressure),
+            ..error
+        })
+    );
+
+    let b6 = tri!(b5.checked_sub(4000).ok_or(overflow(Stage::B6)));
+    let b6_squared = tri!(b6
+        .checked_mul(b6)
+        .and_then(|b6_squared| b6_squared.checked_shr(12))
+        .ok_or(overflow(Stage::B6)));
+
+    let x1 = tri!(b2
+        .checked_mul(b6_squared)
+        .and_then(|x1| x1.checked_shr(11))
+        .ok_or(overflow(Stage::B3)));
+    let x2 = tri!(ac2
+        .checked_mul(b6)
+        .and_then(|x2| x2.checked_shr(11))
+        .ok_or(overflow(Stage::B3)));
+    let x3 = tri!(x1.checked_add(x2).ok_or(overflow(Stage::B3)));
+    let b3 = tri!(ac1
+        .checked_mul(4)
+        .and_then(|b3| b3.checked_add(x3))
+        // in "disable-arithmetic-checks" it is: mode as u8
+        .and_then(|b3| b3.checked_shl(mode as u32))
+        .and_then(|b3| b3.checked_add(2))
+        .and_then(|b3| b3.checked_div(4))
+        .ok_or(overflow(Stage::B3)));
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("B5: {}", b5);
+        defmt::debug!("B6: {}", b6);
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("X3: {}", x3);
+        defmt::debug!("B3: {}", b3);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("B5: {}", b5);
+        log::debug!("B6: {}", b6);
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("X3: {}", x3);
+        log::debug!("B3: {}", b3);
+    }
+
+    let ac3 = calibration.ac3 as i32;
+    let b1 = calibration.b1 as i32;
+    let ac4 = calibration.ac4 as u32;
+
+    let x1 = tri!(ac3
+        .checked_mul(b6)
+        .and_then(|x1| x1.checked_shr(13))
+        .ok_or(overflow(Stage::B4)));
+    let x2 = tri!(b1
+        .checked_mul(b6_squared)
+        .and_then(|x2| x2.checked_shr(16))
+        .ok_or(overflow(Stage::B4)));
+    let x3 = tri!(x1
+        .checked_add(x2)
+        .and_then(|x3| x3.checked_add(2))
+        .and_then(|x3| x3.checked_shr(2))
+        .ok_or(overflow(Stage::B4)));
+    let b4 = tri!(x3
+        .checked_add(32768)
+        .and_then(|x3| ac4.checked_mul(x3 as u32))
+        .and_then(|b4| b4.checked_shr(15))
+        .ok_or(overflow(Stage::B4)));
+    let b7 = tri!((raw_pressure as u32)
+        .checked_sub(b3 as u32)
+        // in "disable-arithmetic-checks" it is: mode as u8
+        .and_then(|b7| b7.checked_mul(50000_u32.checked_shr(mode as u32)?))
+        .ok_or(overflow(Stage::B7)));
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("X3: {}", x3);
+        defmt::debug!("B4: {}", b4);
+        defmt::debug!("B7: {}", b7);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("X3: {}", x3);
+        log::debug!("B4: {}", b4);
+        log::debug!("B7: {}", b7);
+    }
+
+    if b4 == 0 {
+        return Err(error(Stage::Pressure, ArithmeticReason::DivisionByZero));
+    }
+
+    let p = tri!(if b7 < 0x80000000 {
+        b7.checked_mul(2).map(|p| p / b4)
+    } else {
+        (b7 / b4).checked_mul(2)
+    }
+    .ok_or(overflow(Stage::Pressure))) as i32;
+
+    let x1 = tri!((p >> 8)
+        .checked_mul(p >> 8)
+        .and_then(|x1| x1.checked_mul(3038))
+        .and_then(|x1| x1.checked_shr(16))
+        .ok_or(overflow(Stage::Pressure)));
+    let x2 = -tri!(7357_i32
+        .checked_mul(p)
+        .and_then(|x2| x2.checked_shr(16))
+        .ok_or(overflow(Stage::Pressure)));
+
+    let p = tri!(x1
+        .checked_add(x2)
+        .and_then(|x| x.checked_add(3791_i32))
+        .and_then(|x| x.checked_shr(4))
+        .and_then(|x| p.checked_add(x))
+        .ok_or(overflow(Stage::Pressure)));
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("P: {}", p);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("P: {}", p);
+    }
+
+    Ok(p)
+}
+
+/// Compute B5 value.
+#[cfg(feature = "disable-arithmetic-checks")]
+fn compute_b5(calibration: &Calibration, raw_temperature: i16) -> Result<i32, ArithmeticError> {
+    let x1 = ((raw_temperature as i32 - calibration.ac6 as i32) * calibration.ac5 as i32) >> 15;
+    let x2 = ((calibration.mc as i32) << 11) / (x1 + calibration.md as i32);
+
+    Ok(x1 + x2)
+}
+
+/// Compute true temprature in `0.1 C`.
+#[cfg(feature = "disable-arithmetic-checks")]
+pub(crate) fn compute_temperature(
+    calibration: &Calibration,
+    raw_temperature: i16,
+) -> Result<i32, ArithmeticError> {
+    let b5 = tri!(compute_b5(calibration, raw_temperature));
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("Computing temperature");
+        defmt::debug!("Raw temperature: {}", raw_temperature);
+        defmt::debug!("B5: {}", b5);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("Computing temperature");
+        log::debug!("Raw temperature: {}", raw_temperature);
+        log::debug!("B5: {}", b5);
+    }
+
+    Ok((b5 + 8) >> 4)
+}
+
+/// Compute true pressure in `Pa`.
+#[cfg(feature = "disable-arithmetic-checks")]
+pub(crate) fn compute_pressure(
+    calibration: &Calibration,
+    mode: Mode,
+    raw_temperature: i16,
+    raw_pressure: i32,
+) -> Result<i32, ArithmeticError> {
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("Computing pressure");
+        defmt::debug!("Raw temperature: {}", raw_temperature);
+        defmt::debug!("Raw pressure: {}", raw_pressure);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("Computing pressure");
+        log::debug!("Raw temperature: {}", raw_temperature);
+        log::debug!("Raw pressure: {}", raw_pressure);
+    }
+
+    let b5 = tri!(compute_b5(calibration, raw_temperature));
+
+    let b6 = b5 - 4000;
+    let x1 = (calibration.b2 as i32 * ((b6 * b6) >> 12)) >> 11;
+    let x2 = (calibration.ac2 as i32 * b6) >> 11;
+    let x3 = x1 + x2;
+    let b3 = ((((calibration.ac1 as i32) * 4 + x3) << mode as u8) + 2) / 4;
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("B5: {}", b5);
+        defmt::debug!("B6: {}", b6);
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("X3: {}", x3);
+        defmt::debug!("B3: {}", b3);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("B5: {}", b5);
+        log::debug!("B6: {}", b6);
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("X3: {}", x3);
+        log::debug!("B3: {}", b3);
+    }
+
+    let x1 = (calibration.ac3 as i32 * b6) >> 13;
+    let x2 = (calibration.b1 as i32 * ((b6 * b6) >> 12)) >> 16;
+    let x3 = ((x1 + x2) + 2) >> 2;
+    let b4 = ((calibration.ac4 as u32) * ((x3 + 32768) as u32)) >> 15;
+    let b7 = (raw_pressure as u32 - b3 as u32) * (50000 >> mode as u8);
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("X3: {}", x3);
+        defmt::debug!("B4: {}", b4);
+        defmt::debug!("B7: {}", b7);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("X3: {}", x3);
+        log::debug!("B4: {}", b4);
+        log::debug!("B7: {}", b7);
+    }
+
+    let p = if b7 < 0x80000000 {
+        (b7 * 2) / b4
+    } else {
+        (b7 / b4) * 2
+    } as i32;
+
+    let x1 = (p >> 8) * (p >> 8);
+    let x1 = (x1 * 3038) >> 16;
+    let x2 = (-7357 * p) >> 16;
+
+    let p = p + ((x1 + x2 + 3791_i32) >> 4);
+
+    #[cfg(feature = "defmt")]
+    {
+        defmt::debug!("X1: {}", x1);
+        defmt::debug!("X2: {}", x2);
+        defmt::debug!("P: {}", p);
+    }
+
+    #[cfg(feature = "log")]
+    {
+        log::debug!("X1: {}", x1);
+        log::debug!("X2: {}", x2);
+        log::debug!("P: {}", p);
+    }
+
+    Ok(p)
+}
+
+/// Compute the altitude in meters from the pressure in `Pa` and the sea level pressure in `Pa`.
+pub(crate) fn compute_altitude(pressure: i32, sea_level_pressure: f32) -> f32 {
+    44330.0 * (1.0 - libm::powf(pressure as f32 / sea_level_pressure, 0.1903))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calibration data of the datasheet example.
+    fn calibration() -> Calibration {
+        Calibration {
+            ac1: 408,
+            ac2: -72,
+            ac3: -14383,
+            ac4: 32741,
+            ac5: 32757,
+            ac6: 23153,
+            b1: 6190,
+            b2: 4,
+            mb: -32768,
+            mc: -8711,
+            md: 2868,
+        }
+    }
+
+    /// Mode, raw temperature, raw pressure, true temperature and true pressure.
+    ///
+    /// Run with and without the `disable-arithmetic-checks` feature,
+    /// so the checked and unchecked compensations agree on these values.
+    const EXAMPLES: [(Mode, i16, i32, i32, i32); 4] = [
+        (Mode::UltraLowPower, 27898, 23843, 150, 69964),
+        (Mode::Standard, 27950, 47700, 154, 70051),
+        (Mode::HighResolution, 28001, 95400, 158, 70117),
+        (Mode::UltraHighResolution, 27800, 190_800, 142, 69859),
+    ];
+
+    #[test]
+    fn datasheet_example() {
+        for (mode, raw_temperature, raw_pressure, temperature, pressure) in EXAMPLES {
+            assert_eq!(
+                compute_temperature(&calibration(), raw_temperature),
+                Ok(temperature)
+            );
+            assert_eq!(
+                compute_pressure(&calibration(), mode, raw_temperature, raw_pressure),
+                Ok(pressure)
+            );
+        }
+    }
+
+    #[cfg(not(feature = "disable-arithmetic-checks"))]
+    #[test]
+    fn b5_division_by_zero() {
+        // `X1 + MD == 0`.
+        let error = ArithmeticError {
+            stage: Stage::B5,
+            reason: ArithmeticReason::DivisionByZero,
+            raw_temperature: 20285,
+            raw_pressure: None,
+        };
+
+        assert_eq!(compute_temperature(&calibration(), 20285), Err(error));
+        assert_eq!(
+            compute_pressure(&calibration(), Mode::UltraLowPower, 20285, 23843),
+            Err(ArithmeticError {
+                raw_pressure: Some(23843),
+                ..error
+            })
+        );
+    }
+
+    #[cfg(not(feature = "disable-arithmetic-checks"))]
+    #[test]
+    fn b4_zero() {
+        let calibration = Calibration {
+            ac4: 0,
+            ..calibration()
+        };
+
+        assert_eq!(
+            compute_pressure(&calibration, Mode::UltraLowPower, 27898, 23843),
+            Err(ArithmeticError {
+                stage: Stage::Pressure,
+                reason: ArithmeticReason::DivisionByZero,
+                raw_temperature: 27898,
+                raw_pressure: Some(23843),
+            })
+        );
+    }
+
+    #[cfg(not(feature = "disable-arithmetic-checks"))]
+    #[test]
+    fn b7_overflow() {
+        // `UP < B3`.
+        assert_eq!(
+            compute_pressure(&calibration(), Mode::UltraLowPower, 27898, 0),
+            Err(ArithmeticError {
+                stage: Stage::B7,
+                reason: ArithmeticReason::Overflow,
+                raw_temperature: 27898,
+                raw_pressure: Some(0),
+            })
+        );
+    }
+}
@@ -6,7 +6,7 @@ use duplicate::duplicate_item;
 ///
 /// Used to trick the compiler while using [`duplicate_item`] to implement `async` and `blocking` versions of the same module.
 /// Using this trait, we can write normal rust code that can also be formatted by `rustfmt`.
-trait Identity: Sized {
+pub(crate) trait Identity: Sized {
     fn identity(self) -> Self {
         self
     }
@@ -15,9 +15,9 @@ trait Identity: Sized {
 impl<T: Sized> Identity for T {}
 
 #[duplicate_item(
-    feature_        module        async     await               i2c_trait                       delay_trait;
-    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs];
-    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs];
+    feature_        module        async     await               i2c_trait                       delay_trait                             eoc_trait                                   wait_for_eoc;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs]    [embedded_hal_async::digital::Wait]         [wait_for_eoc_async];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs]          [embedded_hal::digital::InputPin]           [wait_for_eoc];
 )]
 pub mod module {
     //! Device definition and implementation.
@@ -27,18 +27,120 @@ pub mod module {
         use super::super::Identity;
 
         use crate::{
-            address::Address, calibration::Calibration, error::BMP180Error, id::Id, mode::Mode,
-            register::Register, tri,
+            address::Address,
+            calibration::Calibration,
+            compensation::{compute_altitude, compute_pressure, compute_temperature},
+            error::{BMP180Error, Operation},
+            health::{HealthMonitor, HealthStatus, Reading},
+            id::Id,
+            mode::Mode,
+            pin::{wait_for_eoc, NoPin},
+            record::{Flags, Record},
+            recovery::{module::BusRecovery, NoRecovery},
+            register::{Register, SCO},
+            retry::RetryPolicy,
+            selftest::{SelfTestReport, MODE_PRESSURE_TOLERANCE, RAW_SAMPLES},
+            tri,
+            units::{DeciCelsius, Meters, Pascal},
         };
 
+        #[cfg(feature = "embedded-storage")]
+        use crate::error::StorageInitError;
+        #[cfg(feature = "statistics")]
+        use crate::statistics::Statistics;
+
+        use embedded_hal::{digital::OutputPin, i2c::Error};
+
+        /// Write `bytes` to the device, retrying according to `policy`.
+        async fn retry_write<I2C, DELAY>(
+            i2c: &mut I2C,
+            delay: &mut DELAY,
+            policy: &RetryPolicy,
+            retries: &mut u32,
+            address: u8,
+            bytes: &[u8],
+        ) -> Result<(), I2C::Error>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+        {
+            let mut attempt = 1;
+
+            loop {
+                match i2c.write(address, bytes).await {
+                    Ok(()) => return Ok(()),
+                    Err(error) if policy.should_retry(attempt, error.kind()) => {
+                        delay.delay_ms(policy.delay_ms(attempt)).await;
+
+                        *retries = retries.saturating_add(1);
+                        attempt += 1;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        /// Write `bytes` to the device and read into `buffer`, retrying according to `policy`.
+        async fn retry_write_read<I2C, DELAY>(
+            i2c: &mut I2C,
+            delay: &mut DELAY,
+            policy: &RetryPolicy,
+            retries: &mut u32,
+            address: u8,
+            bytes: &[u8],
+            buffer: &mut [u8],
+        ) -> Result<(), I2C::Error>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+        {
+            let mut attempt = 1;
+
+            loop {
+                match i2c.write_read(address, bytes, buffer).await {
+                    Ok(()) => return Ok(()),
+                    Err(error) if policy.should_retry(attempt, error.kind()) => {
+                        delay.delay_ms(policy.delay_ms(attempt)).await;
+
+                        *retries = retries.saturating_add(1);
+                        attempt += 1;
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        /// Reset the device using the `XCLR` pin, if set.
+        async fn reset<XCLR, DELAY, E>(
+            xclr: Option<&mut XCLR>,
+            delay: &mut DELAY,
+        ) -> Result<(), BMP180Error<E>>
+        where
+            XCLR: OutputPin,
+            DELAY: delay_trait,
+        {
+            if let Some(xclr) = xclr {
+                tri!(xclr.set_low().map_err(BMP180Error::pin));
+
+                delay.delay_ms(1).await;
+
+                tri!(xclr.set_high().map_err(BMP180Error::pin));
+
+                // Start-up time after reset.
+                delay.delay_ms(10).await;
+            }
+
+            Ok(())
+        }
+
         /// Builder for an uninitialized `BMP180` device.
         ///
         /// Helpful for using default values.
         #[derive(Clone)]
         #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
         #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
-        pub struct UninitBMP180Builder<I2C, DELAY> {
-            inner: UninitBMP180<I2C, DELAY>,
+        pub struct UninitBMP180Builder<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> {
+            inner: UninitBMP180<I2C, DELAY, EOC, XCLR, RECOVERY>,
         }
 
         impl<I2C, DELAY> UninitBMP180Builder<I2C, DELAY>
@@ -52,7 +154,16 @@ pub mod module {
                     inner: UninitBMP180::new(Address::default(), Mode::default(), i2c, delay),
                 }
             }
+        }
 
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> UninitBMP180Builder<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
             /// Set the device address.
             pub fn addr(mut self, addr: Address) -> Self {
                 self.inner.addr = addr;
@@ -65,8 +176,66 @@ pub mod module {
                 self
             }
 
+            /// Set the retry policy applied to each I2C transaction.
+            ///
+            /// By default, failed transactions are not retried.
+            pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+                self.inner.retry_policy = retry_policy;
+                self
+            }
+
+            /// Set the I2C bus recovery.
+            ///
+            /// The bus is recovered after `threshold` consecutive failed I2C transactions,
+            /// before the next update, and the device ID is validated again.
+            pub fn bus_recovery<R>(
+                self,
+                recovery: R,
+                threshold: u8,
+            ) -> UninitBMP180Builder<I2C, DELAY, EOC, XCLR, R>
+            where
+                R: BusRecovery<I2C>,
+            {
+                let mut inner = self
+                    .inner
+                    .map_parts(|eoc, xclr, _| (eoc, xclr, Some(recovery)));
+
+                inner.recovery_threshold = threshold.max(1);
+
+                UninitBMP180Builder { inner }
+            }
+
+            /// Set the `EOC` (end of conversion) pin of a `BMP085` device.
+            ///
+            /// The pin is used to detect the end of a conversion instead of waiting for the maximum conversion time.
+            /// If the pin does not go high within the maximum conversion time, the conversion is assumed to be complete.
+            pub fn eoc<P>(self, eoc: P) -> UninitBMP180Builder<I2C, DELAY, P, XCLR, RECOVERY>
+            where
+                P: eoc_trait,
+            {
+                UninitBMP180Builder {
+                    inner: self
+                        .inner
+                        .map_parts(|_, xclr, recovery| (Some(eoc), xclr, recovery)),
+                }
+            }
+
+            /// Set the `XCLR` (master clear) pin of a `BMP085` device.
+            ///
+            /// The pin is used to reset the device before initialization.
+            pub fn xclr<P>(self, xclr: P) -> UninitBMP180Builder<I2C, DELAY, EOC, P, RECOVERY>
+            where
+                P: OutputPin,
+            {
+                UninitBMP180Builder {
+                    inner: self
+                        .inner
+                        .map_parts(|eoc, _, recovery| (eoc, Some(xclr), recovery)),
+                }
+            }
+
             /// Build the `BMP180` device.
-            pub fn build(self) -> UninitBMP180<I2C, DELAY> {
+            pub fn build(self) -> UninitBMP180<I2C, DELAY, EOC, XCLR, RECOVERY> {
                 self.inner
             }
         }
@@ -77,7 +246,7 @@ pub mod module {
         #[derive(Clone)]
         #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
         #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
-        pub struct UninitBMP180<I2C, DELAY> {
+        pub struct UninitBMP180<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> {
             /// Device I2C address.
             addr: Address,
             /// Device mode.
@@ -86,6 +255,83 @@ pub mod module {
             i2c: I2C,
             /// Delay provider.
             delay: DELAY,
+            /// Optional `EOC` pin of a `BMP085` device.
+            eoc: Option<EOC>,
+            /// Optional `XCLR` pin of a `BMP085` device.
+            xclr: Option<XCLR>,
+            /// Retry policy applied to each I2C transaction.
+            retry_policy: RetryPolicy,
+            /// Number of retries performed.
+            retries: u32,
+            /// Optional I2C bus recovery.
+            recovery: Option<RECOVERY>,
+            /// Number of consecutive failed I2C transactions triggering the bus recovery.
+            recovery_threshold: u8,
+            /// Statistics of the initialization.
+            #[cfg(feature = "statistics")]
+            statistics: Statistics,
+        }
+
+        /// Result of initializing a `BMP180` device.
+        type InitResult<I2C, DELAY, EOC, XCLR, RECOVERY> = Result<
+            BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>,
+            BMP180Error<<I2C as embedded_hal::i2c::ErrorType>::Error>,
+        >;
+
+        /// Result of initializing a `BMP180` device with stored calibration data.
+        ///
+        /// Carries whether the stored calibration data got refreshed.
+        type CalibratedInitResult<I2C, DELAY, EOC, XCLR, RECOVERY> = Result<
+            (BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>, bool),
+            BMP180Error<<I2C as embedded_hal::i2c::ErrorType>::Error>,
+        >;
+
+        /// Result of initializing a `BMP180` device with calibration data persisted in a storage.
+        ///
+        /// Carries whether the stored calibration record got refreshed.
+        #[cfg(feature = "embedded-storage")]
+        type StorageInitResult<I2C, DELAY, EOC, XCLR, RECOVERY, S> = Result<
+            (BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>, bool),
+            StorageInitError<
+                <I2C as embedded_hal::i2c::ErrorType>::Error,
+                <S as embedded_storage::ReadStorage>::Error,
+            >,
+        >;
+
+        /// Uninitialized `BMP085` device.
+        ///
+        /// The `BMP085` shares the register map, device ID and compensation with the `BMP180`,
+        /// use [`UninitBMP180Builder::eoc`] and [`UninitBMP180Builder::xclr`] to set its additional pins.
+        pub type UninitBMP085<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> =
+            UninitBMP180<I2C, DELAY, EOC, XCLR, RECOVERY>;
+
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> UninitBMP180<I2C, DELAY, EOC, XCLR, RECOVERY> {
+            /// Move the fields into a device with other optional pins and bus recovery.
+            fn map_parts<E, X, R>(
+                self,
+                map: impl FnOnce(
+                    Option<EOC>,
+                    Option<XCLR>,
+                    Option<RECOVERY>,
+                ) -> (Option<E>, Option<X>, Option<R>),
+            ) -> UninitBMP180<I2C, DELAY, E, X, R> {
+                let (eoc, xclr, recovery) = map(self.eoc, self.xclr, self.recovery);
+
+                UninitBMP180 {
+                    addr: self.addr,
+                    mode: self.mode,
+                    i2c: self.i2c,
+                    delay: self.delay,
+                    eoc,
+                    xclr,
+                    retry_policy: self.retry_policy,
+                    retries: self.retries,
+                    recovery,
+                    recovery_threshold: self.recovery_threshold,
+                    #[cfg(feature = "statistics")]
+                    statistics: self.statistics,
+                }
+            }
         }
 
         impl<I2C, DELAY> UninitBMP180<I2C, DELAY>
@@ -100,6 +346,14 @@ pub mod module {
                     mode,
                     i2c,
                     delay,
+                    eoc: None,
+                    xclr: None,
+                    retry_policy: RetryPolicy::default(),
+                    retries: 0,
+                    recovery: None,
+                    recovery_threshold: u8::MAX,
+                    #[cfg(feature = "statistics")]
+                    statistics: Statistics::default(),
                 }
             }
 
@@ -107,21 +361,58 @@ pub mod module {
             pub fn builder(i2c: I2C, delay: DELAY) -> UninitBMP180Builder<I2C, DELAY> {
                 UninitBMP180Builder::new(i2c, delay)
             }
+        }
 
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> UninitBMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
             /// Device I2C address as `u8`.
             fn addr_u8(&self) -> u8 {
                 self.addr.into()
             }
 
+            /// Write `bytes` to the device and read into `buffer`, retrying according to the retry policy.
+            async fn write_read(
+                &mut self,
+                bytes: &[u8],
+                buffer: &mut [u8],
+            ) -> Result<(), I2C::Error> {
+                let address = self.addr_u8();
+
+                #[cfg(feature = "statistics")]
+                let retries = self.retries;
+
+                let result = retry_write_read(
+                    &mut self.i2c,
+                    &mut self.delay,
+                    &self.retry_policy,
+                    &mut self.retries,
+                    address,
+                    bytes,
+                    buffer,
+                )
+                .await;
+
+                #[cfg(feature = "statistics")]
+                self.statistics.record_write_read(
+                    bytes.len(),
+                    buffer.len(),
+                    self.retries.wrapping_sub(retries),
+                );
+
+                result
+            }
+
             /// Read device ID.
             async fn read_id(&mut self) -> Result<u8, I2C::Error> {
                 let mut data = [0u8; 2];
 
-                tri!(
-                    self.i2c
-                        .write_read(self.addr_u8(), &[Register::ChipId as u8], &mut data)
-                        .await
-                );
+                tri!(self.write_read(&[Register::ChipId as u8], &mut data).await);
 
                 Ok(data[0])
             }
@@ -136,37 +427,168 @@ pub mod module {
                 let mut data = [0u8; 22];
 
                 tri!(
-                    self.i2c
-                        .write_read(self.addr_u8(), &[Register::CalibrationAc1 as u8], &mut data)
+                    self.write_read(&[Register::CalibrationAc1 as u8], &mut data)
                         .await
                 );
 
                 Ok(Calibration::from_slice(&data))
             }
 
+            /// Check that the stored calibration data belongs to the device.
+            ///
+            /// Only the first (`AC1`) and the last (`MD`) calibration words are read and compared.
+            async fn spot_check_calibration(
+                &mut self,
+                calibration: &Calibration,
+            ) -> Result<bool, I2C::Error> {
+                for (register, expected) in [
+                    (Register::CalibrationAc1, calibration.ac1),
+                    (Register::CalibrationMd, calibration.md),
+                ] {
+                    let mut data = [0u8; 2];
+
+                    tri!(self.write_read(&[register as u8], &mut data).await);
+
+                    if i16::from_be_bytes(data) != expected {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+
+            /// Reset the device using the `XCLR` pin, if set.
+            async fn reset(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                reset(self.xclr.as_mut(), &mut self.delay).await
+            }
+
             /// Initialize `BMP180` device.
-            pub async fn initialize(
+            ///
+            /// The device is reset first if the `XCLR` pin is set.
+            pub async fn initialize(mut self) -> InitResult<I2C, DELAY, EOC, XCLR, RECOVERY> {
+                tri!(self.reset().await);
+
+                let id = tri!(self
+                    .read_id()
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadId)));
+
+                if !Self::validate_id(id) {
+                    return Err(BMP180Error::InvalidId(id));
+                }
+
+                let calibration = tri!(self
+                    .read_calibration()
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadCalibration)));
+
+                Ok(self.into_initialized(calibration))
+            }
+
+            /// Initialize `BMP180` device with previously stored calibration data.
+            ///
+            /// Only the device ID is validated and the stored calibration data is spot-checked against the device,
+            /// by comparing its first (`AC1`) and last (`MD`) words, saving the read of the whole calibration block.
+            /// The calibration data is read from the device if `calibration` is `None`,
+            /// e.g. because [`Calibration::from_record`] detected a CRC mismatch, or if the spot-check fails.
+            ///
+            /// Returns the device and whether the stored calibration data was missing or stale and got refreshed,
+            /// in which case `BMP180::calibration` should be persisted again.
+            pub async fn initialize_with_calibration(
                 mut self,
-            ) -> Result<BMP180<I2C, DELAY>, BMP180Error<I2C::Error>> {
-                let id = tri!(self.read_id().await.map_err(BMP180Error::I2C));
+                calibration: Option<Calibration>,
+            ) -> CalibratedInitResult<I2C, DELAY, EOC, XCLR, RECOVERY> {
+                tri!(self.reset().await);
+
+                let id = tri!(self
+                    .read_id()
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadId)));
 
                 if !Self::validate_id(id) {
                     return Err(BMP180Error::InvalidId(id));
                 }
 
-                let calibration = tri!(self.read_calibration().await.map_err(BMP180Error::I2C));
+                if let Some(calibration) = calibration {
+                    let valid = tri!(self
+                        .spot_check_calibration(&calibration)
+                        .await
+                        .map_err(BMP180Error::i2c(Operation::ReadCalibration)));
+
+                    if valid {
+                        return Ok((self.into_initialized(calibration), false));
+                    }
+                }
+
+                let calibration = tri!(self
+                    .read_calibration()
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadCalibration)));
+
+                Ok((self.into_initialized(calibration), true))
+            }
+
+            /// Initialize `BMP180` device with calibration data persisted at `offset` in `storage`.
+            ///
+            /// See [`UninitBMP180::initialize_with_calibration`].
+            /// The calibration record is written back to `storage` if it was missing or stale.
+            ///
+            /// Returns the device and whether the stored calibration record got refreshed.
+            ///
+            /// Only available when the `embedded-storage` feature is enabled.
+            #[cfg(feature = "embedded-storage")]
+            pub async fn initialize_with_storage<S>(
+                self,
+                storage: &mut S,
+                offset: u32,
+            ) -> StorageInitResult<I2C, DELAY, EOC, XCLR, RECOVERY, S>
+            where
+                S: embedded_storage::Storage,
+            {
+                let calibration =
+                    tri!(Calibration::load(storage, offset).map_err(StorageInitError::Storage));
+
+                let (device, refreshed) = tri!(self
+                    .initialize_with_calibration(calibration)
+                    .await
+                    .map_err(StorageInitError::Device));
+
+                if refreshed {
+                    tri!(device
+                        .calibration()
+                        .store(storage, offset)
+                        .map_err(StorageInitError::Storage));
+                }
 
-                let bmp180 = BMP180 {
+                Ok((device, refreshed))
+            }
+
+            /// Create the initialized `BMP180` device.
+            fn into_initialized(
+                self,
+                calibration: Calibration,
+            ) -> BMP180<I2C, DELAY, EOC, XCLR, RECOVERY> {
+                BMP180 {
                     addr: self.addr,
                     mode: self.mode,
                     calibration,
                     temperature: 0,
                     pressure: 0,
+                    raw_temperature: 0,
+                    raw_pressure: 0,
+                    compensation_failed: false,
                     i2c: self.i2c,
                     delay: self.delay,
-                };
-
-                Ok(bmp180)
+                    eoc: self.eoc,
+                    xclr: self.xclr,
+                    retry_policy: self.retry_policy,
+                    retries: self.retries,
+                    recovery: self.recovery,
+                    recovery_threshold: self.recovery_threshold,
+                    failures: 0,
+                    #[cfg(feature = "statistics")]
+                    statistics: self.statistics,
+                }
             }
         }
 
@@ -176,20 +598,41 @@ pub mod module {
         #[derive(Clone)]
         #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
         #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
-        pub struct BMP180<I2C, DELAY> {
+        pub struct BMP180<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> {
             addr: Address,
             mode: Mode,
             calibration: Calibration,
             temperature: i32,
             pressure: i32,
+            raw_temperature: i16,
+            raw_pressure: i32,
+            compensation_failed: bool,
             i2c: I2C,
             delay: DELAY,
+            eoc: Option<EOC>,
+            xclr: Option<XCLR>,
+            retry_policy: RetryPolicy,
+            retries: u32,
+            recovery: Option<RECOVERY>,
+            recovery_threshold: u8,
+            failures: u8,
+            #[cfg(feature = "statistics")]
+            statistics: Statistics,
         }
 
-        impl<I2C, DELAY> BMP180<I2C, DELAY>
+        /// `BMP085` device.
+        ///
+        /// See [`UninitBMP085`].
+        pub type BMP085<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> =
+            BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>;
+
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
         where
             I2C: i2c_trait,
             DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
         {
             /// Device I2C address.
             pub fn addr(&self) -> Address {
@@ -201,6 +644,115 @@ pub mod module {
                 self.addr.into()
             }
 
+            /// Write `bytes` to the device, retrying according to the retry policy.
+            async fn write(&mut self, bytes: &[u8]) -> Result<(), I2C::Error> {
+                let address = self.addr_u8();
+
+                #[cfg(feature = "statistics")]
+                let retries = self.retries;
+
+                let result = retry_write(
+                    &mut self.i2c,
+                    &mut self.delay,
+                    &self.retry_policy,
+                    &mut self.retries,
+                    address,
+                    bytes,
+                )
+                .await;
+
+                #[cfg(feature = "statistics")]
+                self.statistics
+                    .record_write(bytes.len(), self.retries.wrapping_sub(retries));
+
+                self.track_failures(result)
+            }
+
+            /// Write `bytes` to the device and read into `buffer`, retrying according to the retry policy.
+            async fn write_read(
+                &mut self,
+                bytes: &[u8],
+                buffer: &mut [u8],
+            ) -> Result<(), I2C::Error> {
+                let address = self.addr_u8();
+
+                #[cfg(feature = "statistics")]
+                let retries = self.retries;
+
+                let result = retry_write_read(
+                    &mut self.i2c,
+                    &mut self.delay,
+                    &self.retry_policy,
+                    &mut self.retries,
+                    address,
+                    bytes,
+                    buffer,
+                )
+                .await;
+
+                #[cfg(feature = "statistics")]
+                self.statistics.record_write_read(
+                    bytes.len(),
+                    buffer.len(),
+                    self.retries.wrapping_sub(retries),
+                );
+
+                self.track_failures(result)
+            }
+
+            /// Count consecutive failed I2C transactions.
+            fn track_failures<T>(
+                &mut self,
+                result: Result<T, I2C::Error>,
+            ) -> Result<T, I2C::Error> {
+                match result {
+                    Ok(_) => self.failures = 0,
+                    Err(_) => self.failures = self.failures.saturating_add(1),
+                }
+
+                result
+            }
+
+            /// Recover the I2C bus if set and the number of consecutive failed I2C transactions reached the threshold.
+            async fn recover_if_needed(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                if self.recovery.is_some() && self.failures >= self.recovery_threshold {
+                    tri!(self.recover().await);
+                }
+
+                Ok(())
+            }
+
+            /// Recover the I2C bus and validate the device ID again.
+            ///
+            /// Does nothing if no bus recovery is set.
+            pub async fn recover_bus(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                let result = self.recover().await;
+
+                self.record_failure(result)
+            }
+
+            /// Recover the I2C bus and validate the device ID again.
+            async fn recover(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                let Some(recovery) = self.recovery.as_mut() else {
+                    return Ok(());
+                };
+
+                recovery.recover(&mut self.i2c).await;
+
+                let mut data = [0u8; 1];
+
+                tri!(self
+                    .write_read(&[Register::ChipId as u8], &mut data)
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadId)));
+
+                if !Id::is_valid(data[0]) {
+                    return Err(BMP180Error::InvalidId(data[0]));
+                }
+
+                Ok(())
+            }
+
             /// Device operating mode.
             pub fn mode(&self) -> Mode {
                 self.mode
@@ -211,6 +763,42 @@ pub mod module {
                 &self.calibration
             }
 
+            /// Retry policy applied to each I2C transaction.
+            pub fn retry_policy(&self) -> &RetryPolicy {
+                &self.retry_policy
+            }
+
+            /// Set the retry policy applied to each I2C transaction.
+            pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+                self.retry_policy = retry_policy;
+            }
+
+            /// Number of retries performed since initialization, including the retries during initialization.
+            pub fn retries(&self) -> u32 {
+                self.retries
+            }
+
+            /// Reset the number of retries performed.
+            pub fn reset_retries(&mut self) {
+                self.retries = 0;
+            }
+
+            /// Device statistics.
+            ///
+            /// Only available when the `statistics` feature is enabled.
+            #[cfg(feature = "statistics")]
+            pub fn statistics(&self) -> &Statistics {
+                &self.statistics
+            }
+
+            /// Reset the device statistics.
+            ///
+            /// Only available when the `statistics` feature is enabled.
+            #[cfg(feature = "statistics")]
+            pub fn reset_statistics(&mut self) {
+                self.statistics = Statistics::default();
+            }
+
             /// True temperature in `0.1 C` according to the calibration data.
             pub fn temperature(&self) -> i32 {
                 self.temperature
@@ -226,6 +814,51 @@ pub mod module {
                 self.pressure
             }
 
+            /// Last raw temperature (`UT`) read from the device.
+            pub fn raw_temperature(&self) -> i16 {
+                self.raw_temperature
+            }
+
+            /// Last raw pressure (`UP`) read from the device.
+            pub fn raw_pressure(&self) -> i32 {
+                self.raw_pressure
+            }
+
+            /// Check the health of the current readings using `monitor`.
+            ///
+            /// Should be called once after each [`update`](Self::update).
+            pub fn check_health(&self, monitor: &mut HealthMonitor) -> HealthStatus {
+                monitor.check(Reading {
+                    temperature: self.temperature(),
+                    pressure: self.pressure(),
+                    raw_temperature: self.raw_temperature(),
+                    raw_pressure: self.raw_pressure(),
+                })
+            }
+
+            /// Log record of the last readings, see [`Record`].
+            ///
+            /// The flags carry `health`, usually the status returned by [`check_health`](Self::check_health)
+            /// for the same readings, and whether the compensation of the last readings failed.
+            /// The true temperature is saturated to fit the record.
+            pub fn to_record(&self, timestamp: u32, health: HealthStatus) -> Record {
+                let mut flags = Flags::from_health(health);
+
+                if self.compensation_failed {
+                    flags = flags | Flags::COMPENSATION_FAILED;
+                }
+
+                Record {
+                    timestamp,
+                    mode: self.mode,
+                    flags,
+                    raw_temperature: self.raw_temperature,
+                    raw_pressure: self.raw_pressure,
+                    temperature: self.temperature.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                    pressure: self.pressure,
+                }
+            }
+
             /// Pressure in `Pa` at sea level.
             pub fn sea_level_pressure(&self, altitude_meters: f32) -> i32 {
                 let pressure = self.pressure() as f32;
@@ -237,427 +870,504 @@ pub mod module {
             ///
             /// Standard pressure at sea level is `101325 Pa`.
             pub fn altitude(&self, sea_level_pressure: f32) -> f32 {
-                let pressure = self.pressure();
+                compute_altitude(self.pressure(), sea_level_pressure)
+            }
+
+            /// True temperature according to the calibration data.
+            pub fn typed_temperature(&self) -> DeciCelsius {
+                DeciCelsius(self.temperature())
+            }
+
+            /// True pressure according to the calibration data.
+            pub fn typed_pressure(&self) -> Pascal {
+                Pascal(self.pressure())
+            }
 
-                44330.0 * (1.0 - libm::powf(pressure as f32 / sea_level_pressure, 0.1903))
+            /// Pressure at sea level.
+            pub fn typed_sea_level_pressure(&self, altitude: Meters) -> Pascal {
+                Pascal(self.sea_level_pressure(altitude.0))
+            }
+
+            /// Altitude.
+            ///
+            /// Standard pressure at sea level is `101325 Pa`.
+            pub fn typed_altitude(&self, sea_level_pressure: Pascal) -> Meters {
+                Meters(self.altitude(sea_level_pressure.0 as f32))
+            }
+
+            /// Reset the device using the `XCLR` pin, if set.
+            ///
+            /// The calibration data and the last readings are kept.
+            pub async fn reset(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                let result = reset(self.xclr.as_mut(), &mut self.delay).await;
+
+                self.record_failure(result)
+            }
+
+            /// Wait for the end of a conversion.
+            ///
+            /// Uses the `EOC` pin if set, otherwise waits for the maximum conversion time `max_ms`.
+            async fn wait_for_conversion(
+                &mut self,
+                max_ms: u32,
+            ) -> Result<(), BMP180Error<I2C::Error>> {
+                match self.eoc.as_mut() {
+                    Some(eoc) => {
+                        tri!(wait_for_eoc(eoc, &mut self.delay, max_ms)
+                            .await
+                            .map_err(BMP180Error::pin));
+                    }
+                    None => self.delay.delay_ms(max_ms).await,
+                }
+
+                Ok(())
             }
 
             /// Read raw temperature.
             async fn read_raw_temperature(&mut self) -> Result<i16, BMP180Error<I2C::Error>> {
                 tri!(self
-                    .i2c
-                    .write(
-                        self.addr_u8(),
-                        &[Register::Control as u8, Register::ReadTempCmd as u8]
-                    )
+                    .write(&[Register::Control as u8, Register::ReadTempCmd as u8])
                     .await
-                    .map_err(BMP180Error::I2C));
+                    .map_err(BMP180Error::i2c(Operation::StartTemperature)));
 
-                self.delay.delay_ms(5).await;
+                tri!(self.wait_for_conversion(5).await);
 
                 let mut data = [0u8; 2];
 
                 tri!(self
-                    .i2c
-                    .write_read(
-                        self.addr_u8(),
-                        &[Register::TempPressureData as u8],
-                        &mut data
-                    )
+                    .write_read(&[Register::TempPressureData as u8], &mut data)
                     .await
-                    .map_err(BMP180Error::I2C));
+                    .map_err(BMP180Error::i2c(Operation::ReadTemperature)));
 
-                let raw_temperature = ((data[0] as i16) << 8) | data[1] as i16;
-
-                Ok(raw_temperature)
+                Ok(((data[0] as i16) << 8) | data[1] as i16)
             }
 
-            /// Read raw pressure.
-            async fn read_raw_pressure(&mut self) -> Result<i32, BMP180Error<I2C::Error>> {
-                let mode = self.mode();
-
+            /// Read raw pressure in the given mode.
+            async fn read_raw_pressure(
+                &mut self,
+                mode: Mode,
+            ) -> Result<i32, BMP180Error<I2C::Error>> {
                 tri!(self
-                    .i2c
-                    .write(
-                        self.addr_u8(),
-                        &[
-                            Register::Control as u8,
-                            Register::ReadPressureCmd as u8 + ((mode as u8) << 6)
-                        ],
-                    )
+                    .write(&[
+                        Register::Control as u8,
+                        Register::ReadPressureCmd as u8 + ((mode as u8) << 6)
+                    ],)
                     .await
-                    .map_err(BMP180Error::I2C));
+                    .map_err(BMP180Error::i2c(Operation::StartPressure)));
 
-                self.delay.delay_ms(mode.delay_ms()).await;
+                tri!(self.wait_for_conversion(mode.delay_ms()).await);
 
                 let mut data = [0u8; 3];
 
                 tri!(self
-                    .i2c
-                    .write_read(
-                        self.addr_u8(),
-                        &[Register::TempPressureData as u8],
-                        &mut data
-                    )
+                    .write_read(&[Register::TempPressureData as u8], &mut data)
                     .await
-                    .map_err(BMP180Error::I2C));
+                    .map_err(BMP180Error::i2c(Operation::ReadPressure)));
 
-                let raw_pressure =
+                Ok(
                     (((data[0] as i32) << 16) + ((data[1] as i32) << 8) + data[2] as i32)
-                        >> (8 - mode as u8);
-
-                Ok(raw_pressure)
+                        >> (8 - mode as u8),
+                )
             }
 
             /// Update temperature in `self`.
             pub async fn update_temperature(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
-                let raw_temperature = tri!(self.read_raw_temperature().await);
+                let result = self.measure_temperature().await;
 
-                self.temperature = tri!(self
-                    .compute_temperature(raw_temperature)
-                    .ok_or(BMP180Error::Arithmetic));
-
-                Ok(())
+                self.record(result)
             }
 
             /// Update pressure in `self`.
             pub async fn update_pressure(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
-                let raw_temperature = tri!(self.read_raw_temperature().await);
-                let raw_pressure = tri!(self.read_raw_pressure().await);
-
-                self.pressure = tri!(self
-                    .compute_pressure(raw_temperature, raw_pressure)
-                    .ok_or(BMP180Error::Arithmetic));
+                let result = self.measure_pressure().await;
 
-                Ok(())
+                self.record(result)
             }
 
             /// Update both temperature and pressure in `self`.
             pub async fn update(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
-                let raw_temperature = tri!(self.read_raw_temperature().await);
-                let raw_pressure = tri!(self.read_raw_pressure().await);
+                let result = self.measure().await;
+
+                self.record(result)
+            }
 
-                self.temperature = tri!(self
-                    .compute_temperature(raw_temperature)
-                    .ok_or(BMP180Error::Arithmetic));
+            /// Record the result of an update in the statistics and whether the compensation failed.
+            fn record(
+                &mut self,
+                result: Result<(), BMP180Error<I2C::Error>>,
+            ) -> Result<(), BMP180Error<I2C::Error>> {
+                match result {
+                    Ok(()) => self.compensation_failed = false,
+                    Err(BMP180Error::Arithmetic(_)) => self.compensation_failed = true,
+                    Err(_) => {}
+                }
 
-                self.pressure = tri!(self
-                    .compute_pressure(raw_temperature, raw_pressure)
-                    .ok_or(BMP180Error::Arithmetic));
+                #[cfg(feature = "statistics")]
+                self.statistics.record_update(&result);
 
-                Ok(())
+                result
             }
-        }
 
-        #[cfg(not(feature = "disable-arithmetic-checks"))]
-        impl<I2C, DELAY> BMP180<I2C, DELAY>
-        where
-            I2C: i2c_trait,
-            DELAY: delay_trait,
-        {
-            /// Compute B5 value.
-            fn compute_b5(&self, raw_temperature: i16) -> Option<i32> {
-                let calibration = self.calibration();
+            /// Record the error of a failed operation other than an update in the statistics.
+            fn record_failure(
+                &mut self,
+                result: Result<(), BMP180Error<I2C::Error>>,
+            ) -> Result<(), BMP180Error<I2C::Error>> {
+                #[cfg(feature = "statistics")]
+                if let Err(error) = &result {
+                    self.statistics.record_error(error);
+                }
+
+                result
+            }
 
-                let rt = raw_temperature as i32;
-                let ac6 = calibration.ac6 as i32;
-                let ac5 = calibration.ac5 as i32;
-                let mc = calibration.mc as i32;
-                let md = calibration.md as i32;
+            /// Measure temperature.
+            async fn measure_temperature(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                tri!(self.recover_if_needed().await);
 
-                let x1 = rt.checked_sub(ac6)?.checked_mul(ac5)?.checked_shr(15)?;
+                let raw_temperature = tri!(self.read_raw_temperature().await);
 
-                let x2 = mc.checked_shl(11)?.checked_div(x1.checked_add(md)?)?;
+                self.raw_temperature = raw_temperature;
+                self.temperature = tri!(compute_temperature(&self.calibration, raw_temperature)
+                    .map_err(BMP180Error::Arithmetic));
 
-                Some(x1 + x2)
+                Ok(())
             }
 
-            /// Compute true temprature in `0.1 C`.
-            fn compute_temperature(&self, raw_temperature: i16) -> Option<i32> {
-                let b5 = self.compute_b5(raw_temperature)?;
+            /// Measure pressure.
+            async fn measure_pressure(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                tri!(self.recover_if_needed().await);
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("Computing temperature");
-                    defmt::debug!("Raw temperature: {}", raw_temperature);
-                    defmt::debug!("B5: {}", b5);
-                }
+                let raw_temperature = tri!(self.read_raw_temperature().await);
+                let raw_pressure = tri!(self.read_raw_pressure(self.mode).await);
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Computing temperature");
-                    log::debug!("Raw temperature: {}", raw_temperature);
-                    log::debug!("B5: {}", b5);
-                }
+                self.raw_temperature = raw_temperature;
+                self.raw_pressure = raw_pressure;
 
-                let temperature = b5.checked_add(8)?;
-                let temperature = temperature.checked_shr(4)?;
+                self.pressure = tri!(compute_pressure(
+                    &self.calibration,
+                    self.mode,
+                    raw_temperature,
+                    raw_pressure
+                )
+                .map_err(BMP180Error::Arithmetic));
 
-                Some(temperature)
+                Ok(())
             }
 
-            /// Compute true pressure in `Pa`.
-            fn compute_pressure(&self, raw_temperature: i16, raw_pressure: i32) -> Option<i32> {
-                let calibration = self.calibration();
-                let mode = self.mode();
+            /// Measure both temperature and pressure.
+            async fn measure(&mut self) -> Result<(), BMP180Error<I2C::Error>> {
+                tri!(self.recover_if_needed().await);
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("Computing pressure");
-                    defmt::debug!("Raw temperature: {}", raw_temperature);
-                    defmt::debug!("Raw pressure: {}", raw_pressure);
-                }
+                let raw_temperature = tri!(self.read_raw_temperature().await);
+                let raw_pressure = tri!(self.read_raw_pressure(self.mode).await);
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Computing pressure");
-                    log::debug!("Raw temperature: {}", raw_temperature);
-                    log::debug!("Raw pressure: {}", raw_pressure);
-                }
+                self.raw_temperature = raw_temperature;
+                self.raw_pressure = raw_pressure;
 
-                let b2 = calibration.b2 as i32;
-                let ac2 = calibration.ac2 as i32;
-                let ac1 = calibration.ac1 as i32;
-
-                let b5 = self.compute_b5(raw_temperature)?;
-
-                let b6 = b5.checked_sub(4000)?;
-                let x1 = b2
-                    .checked_mul(b6.checked_mul(b6)?.checked_shr(12)?)?
-                    .checked_shr(11)?;
-                let x2 = ac2.checked_mul(b6)?.checked_shr(11)?;
-                let x3 = x1.checked_add(x2)?;
-                let b3 = ac1
-                    .checked_mul(4)?
-                    .checked_add(x3)?
-                    // in "disable-arithmetic-checks" it is: mode as u8
-                    .checked_shl(mode as u32)?
-                    .checked_add(2)?
-                    .checked_div(4)?;
-
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("B5: {}", b5);
-                    defmt::debug!("B6: {}", b6);
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("X3: {}", x3);
-                    defmt::debug!("B3: {}", b3);
-                }
+                self.temperature = tri!(compute_temperature(&self.calibration, raw_temperature)
+                    .map_err(BMP180Error::Arithmetic));
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("B5: {}", b5);
-                    log::debug!("B6: {}", b6);
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("X3: {}", x3);
-                    log::debug!("B3: {}", b3);
-                }
+                self.pressure = tri!(compute_pressure(
+                    &self.calibration,
+                    self.mode,
+                    raw_temperature,
+                    raw_pressure
+                )
+                .map_err(BMP180Error::Arithmetic));
 
-                let ac3 = calibration.ac3 as i32;
-                let b1 = calibration.b1 as i32;
-                let ac4 = calibration.ac4 as u32;
-
-                let x1 = ac3.checked_mul(b6)?.checked_shr(13)?;
-                let x2 = b1
-                    .checked_mul(b6.checked_mul(b6)?.checked_shr(12)?)?
-                    .checked_shr(16)?;
-                let x3 = x1.checked_add(x2)?.checked_add(2)?.checked_shr(2)?;
-                let b4 = ac4
-                    .checked_mul(x3.checked_add(32768)? as u32)?
-                    .checked_shr(15)?;
-                let b7 = (raw_pressure as u32)
-                    .checked_sub(b3 as u32)?
-                    // in "disable-arithmetic-checks" it is: mode as u8
-                    .checked_mul(50000_u32.checked_shr(mode as u32)?)?;
-
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("X3: {}", x3);
-                    defmt::debug!("B4: {}", b4);
-                    defmt::debug!("B7: {}", b7);
-                }
+                Ok(())
+            }
 
-                #[cfg(feature = "log")]
+            /// Run a self-test.
+            ///
+            /// Checks the device ID, the calibration data, that the raw values change between conversions,
+            /// that all modes produce consistent pressures and that the conversions complete within the maximum conversion time.
+            ///
+            /// The readings, the mode, the retry counter and the statistics of `self` are left untouched.
+            pub async fn self_test(&mut self) -> Result<SelfTestReport, BMP180Error<I2C::Error>> {
+                let retries = self.retries;
+                let failures = self.failures;
+                #[cfg(feature = "statistics")]
+                let statistics = self.statistics.clone();
+
+                let result = self.run_self_test().await;
+
+                self.retries = retries;
+                self.failures = failures;
+                #[cfg(feature = "statistics")]
                 {
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("X3: {}", x3);
-                    log::debug!("B4: {}", b4);
-                    log::debug!("B7: {}", b7);
+                    self.statistics = statistics;
                 }
 
-                let p = if b7 < 0x80000000 {
-                    b7.checked_mul(2)?.checked_div(b4)?
-                } else {
-                    b7.checked_div(b4)?.checked_mul(2)?
-                } as i32;
+                result
+            }
 
-                let x1 = p.checked_shr(8)?.checked_mul(p.checked_shr(8)?)?;
-                let x1 = x1.checked_mul(3038)?.checked_shr(16)?;
-                let x2 = -7357_i32.checked_mul(p)?.checked_shr(16)?;
+            /// Run the checks of [`self_test`](Self::self_test).
+            async fn run_self_test(&mut self) -> Result<SelfTestReport, BMP180Error<I2C::Error>> {
+                let mut id = [0u8; 1];
 
-                let p =
-                    p.checked_add(x1.checked_add(x2)?.checked_add(3791_i32)?.checked_shr(4)?)?;
+                tri!(self
+                    .write_read(&[Register::ChipId as u8], &mut id)
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadId)));
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("P: {}", p);
-                }
+                let mut raw_temperatures = [0i16; RAW_SAMPLES];
+                let mut raw_pressures = [0i32; RAW_SAMPLES];
 
-                #[cfg(feature = "log")]
+                for (raw_temperature, raw_pressure) in
+                    raw_temperatures.iter_mut().zip(raw_pressures.iter_mut())
                 {
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("P: {}", p);
+                    *raw_temperature = tri!(self.read_raw_temperature().await);
+                    *raw_pressure = tri!(self.read_raw_pressure(self.mode).await);
                 }
 
-                Some(p)
-            }
-        }
+                let temperature_timing = tri!(
+                    self.check_conversion_timing(
+                        Register::ReadTempCmd as u8,
+                        5,
+                        Operation::StartTemperature
+                    )
+                    .await
+                );
 
-        #[cfg(feature = "disable-arithmetic-checks")]
-        impl<I2C, DELAY> BMP180<I2C, DELAY>
-        where
-            I2C: i2c_trait,
-            DELAY: delay_trait,
-        {
-            /// Compute B5 value.
-            fn compute_b5(&self, raw_temperature: i16) -> Option<i32> {
-                let calibration = self.calibration();
+                let mut mode_pressures = [0i32; 4];
+                let mut pressure_timing = [false; 4];
 
-                let x1 = ((raw_temperature as i32 - calibration.ac6 as i32)
-                    * calibration.ac5 as i32)
-                    >> 15;
-                let x2 = ((calibration.mc as i32) << 11) / (x1 + calibration.md as i32);
+                tri!(
+                    self.test_modes(&mut mode_pressures, &mut pressure_timing)
+                        .await
+                );
 
-                Some(x1 + x2)
+                let min = mode_pressures.iter().min().copied().unwrap_or_default();
+                let max = mode_pressures.iter().max().copied().unwrap_or_default();
+
+                Ok(SelfTestReport {
+                    id: id[0],
+                    id_valid: Id::is_valid(id[0]),
+                    calibration_valid: self.calibration.is_valid(),
+                    raw_temperature_changes: raw_temperatures
+                        .windows(2)
+                        .any(|pair| pair[0] != pair[1]),
+                    raw_pressure_changes: raw_pressures.windows(2).any(|pair| pair[0] != pair[1]),
+                    mode_pressures,
+                    modes_consistent: max - min <= MODE_PRESSURE_TOLERANCE,
+                    temperature_timing,
+                    pressure_timing,
+                })
             }
 
-            /// Compute true temprature in `0.1 C`.
-            fn compute_temperature(&self, raw_temperature: i16) -> Option<i32> {
-                let b5 = self.compute_b5(raw_temperature)?;
+            /// Measure the pressure and check the conversion timing in each mode.
+            async fn test_modes(
+                &mut self,
+                pressures: &mut [i32; 4],
+                timing: &mut [bool; 4],
+            ) -> Result<(), BMP180Error<I2C::Error>> {
+                for mode in Mode::ALL {
+                    timing[mode as usize] = tri!(
+                        self.check_conversion_timing(
+                            Register::ReadPressureCmd as u8 + ((mode as u8) << 6),
+                            mode.delay_ms(),
+                            Operation::StartPressure,
+                        )
+                        .await
+                    );
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("Computing temperature");
-                    defmt::debug!("Raw temperature: {}", raw_temperature);
-                    defmt::debug!("B5: {}", b5);
-                }
+                    let raw_temperature = tri!(self.read_raw_temperature().await);
+                    let raw_pressure = tri!(self.read_raw_pressure(mode).await);
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Computing temperature");
-                    log::debug!("Raw temperature: {}", raw_temperature);
-                    log::debug!("B5: {}", b5);
+                    pressures[mode as usize] = tri!(compute_pressure(
+                        &self.calibration,
+                        mode,
+                        raw_temperature,
+                        raw_pressure
+                    )
+                    .map_err(BMP180Error::Arithmetic));
                 }
 
-                Some((b5 + 8) >> 4)
+                Ok(())
             }
 
-            /// Compute true pressure in `Pa`.
-            fn compute_pressure(&self, raw_temperature: i16, raw_pressure: i32) -> Option<i32> {
-                let calibration = self.calibration();
-                let mode = self.mode();
+            /// Start a conversion using `command` and check that it completes within `max_ms`.
+            ///
+            /// The `EOC` pin is not used, the conversion state is read from the control register.
+            async fn check_conversion_timing(
+                &mut self,
+                command: u8,
+                max_ms: u32,
+                operation: Operation,
+            ) -> Result<bool, BMP180Error<I2C::Error>> {
+                tri!(self
+                    .write(&[Register::Control as u8, command])
+                    .await
+                    .map_err(BMP180Error::i2c(operation)));
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("Computing pressure");
-                    defmt::debug!("Raw temperature: {}", raw_temperature);
-                    defmt::debug!("Raw pressure: {}", raw_pressure);
-                }
+                let started = tri!(self.is_converting().await);
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Computing pressure");
-                    log::debug!("Raw temperature: {}", raw_temperature);
-                    log::debug!("Raw pressure: {}", raw_pressure);
-                }
+                self.delay.delay_ms(max_ms).await;
 
-                let b5 = self.compute_b5(raw_temperature)?;
+                let completed = !tri!(self.is_converting().await);
 
-                let b6 = b5 - 4000;
-                let x1 = (calibration.b2 as i32 * ((b6 * b6) >> 12)) >> 11;
-                let x2 = (calibration.ac2 as i32 * b6) >> 11;
-                let x3 = x1 + x2;
-                let b3 = ((((calibration.ac1 as i32) * 4 + x3) << mode as u8) + 2) / 4;
+                Ok(started && completed)
+            }
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("B5: {}", b5);
-                    defmt::debug!("B6: {}", b6);
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("X3: {}", x3);
-                    defmt::debug!("B3: {}", b3);
-                }
+            /// Whether a conversion is running.
+            async fn is_converting(&mut self) -> Result<bool, BMP180Error<I2C::Error>> {
+                let mut control = [0u8; 1];
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("B5: {}", b5);
-                    log::debug!("B6: {}", b6);
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("X3: {}", x3);
-                    log::debug!("B3: {}", b3);
-                }
+                tri!(self
+                    .write_read(&[Register::Control as u8], &mut control)
+                    .await
+                    .map_err(BMP180Error::i2c(Operation::ReadStatus)));
 
-                let x1 = (calibration.ac3 as i32 * b6) >> 13;
-                let x2 = (calibration.b1 as i32 * ((b6 * b6) >> 12)) >> 16;
-                let x3 = ((x1 + x2) + 2) >> 2;
-                let b4 = ((calibration.ac4 as u32) * ((x3 + 32768) as u32)) >> 15;
-                let b7 = (raw_pressure as u32 - b3 as u32) * (50000 >> mode as u8);
+                Ok(control[0] & SCO != 0)
+            }
+        }
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("X3: {}", x3);
-                    defmt::debug!("B4: {}", b4);
-                    defmt::debug!("B7: {}", b7);
-                }
+        #[cfg(feature = "uom")]
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
+            /// True temperature according to the calibration data.
+            ///
+            /// Only available when the `uom` feature is enabled.
+            pub fn uom_temperature(&self) -> uom::si::f32::ThermodynamicTemperature {
+                uom::si::f32::ThermodynamicTemperature::new::<
+                    uom::si::thermodynamic_temperature::degree_celsius,
+                >(self.temperature_celsius())
+            }
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("X3: {}", x3);
-                    log::debug!("B4: {}", b4);
-                    log::debug!("B7: {}", b7);
-                }
+            /// True pressure according to the calibration data.
+            ///
+            /// Only available when the `uom` feature is enabled.
+            pub fn uom_pressure(&self) -> uom::si::f32::Pressure {
+                uom::si::f32::Pressure::new::<uom::si::pressure::pascal>(self.pressure() as f32)
+            }
 
-                let p = if b7 < 0x80000000 {
-                    (b7 * 2) / b4
-                } else {
-                    (b7 / b4) * 2
-                } as i32;
+            /// Pressure at sea level.
+            ///
+            /// Only available when the `uom` feature is enabled.
+            pub fn uom_sea_level_pressure(
+                &self,
+                altitude: uom::si::f32::Length,
+            ) -> uom::si::f32::Pressure {
+                let altitude_meters = altitude.get::<uom::si::length::meter>();
+
+                uom::si::f32::Pressure::new::<uom::si::pressure::pascal>(
+                    self.sea_level_pressure(altitude_meters) as f32,
+                )
+            }
 
-                let x1 = (p >> 8) * (p >> 8);
-                let x1 = (x1 * 3038) >> 16;
-                let x2 = (-7357 * p) >> 16;
+            /// Altitude.
+            ///
+            /// Standard pressure at sea level is `101325 Pa`.
+            ///
+            /// Only available when the `uom` feature is enabled.
+            pub fn uom_altitude(
+                &self,
+                sea_level_pressure: uom::si::f32::Pressure,
+            ) -> uom::si::f32::Length {
+                let sea_level_pressure = sea_level_pressure.get::<uom::si::pressure::pascal>();
+
+                uom::si::f32::Length::new::<uom::si::length::meter>(
+                    self.altitude(sea_level_pressure),
+                )
+            }
+        }
 
-                let p = p + ((x1 + x2 + 3791_i32) >> 4);
+        /// Parts of a `BMP180` device, see [`BMP180::into_parts_full`].
+        ///
+        /// Only available when the `i-know-what-i-am-doing` feature is enabled.
+        #[cfg(feature = "i-know-what-i-am-doing")]
+        pub struct BMP180Parts<I2C, DELAY, EOC = NoPin, XCLR = NoPin, RECOVERY = NoRecovery> {
+            /// Device I2C address.
+            pub addr: Address,
+            /// Device mode.
+            pub mode: Mode,
+            /// Device calibration data.
+            pub calibration: Calibration,
+            /// Last temperature in `0.1 C`.
+            pub temperature: i32,
+            /// Last pressure in `Pa`.
+            pub pressure: i32,
+            /// Last raw temperature (`UT`).
+            pub raw_temperature: i16,
+            /// Last raw pressure (`UP`).
+            pub raw_pressure: i32,
+            /// Device I2C bus.
+            pub i2c: I2C,
+            /// Delay provider.
+            pub delay: DELAY,
+            /// Optional `EOC` pin of a `BMP085` device.
+            pub eoc: Option<EOC>,
+            /// Optional `XCLR` pin of a `BMP085` device.
+            pub xclr: Option<XCLR>,
+            /// Retry policy applied to each I2C transaction.
+            pub retry_policy: RetryPolicy,
+            /// Optional I2C bus recovery.
+            pub recovery: Option<RECOVERY>,
+            /// Number of consecutive failed I2C transactions triggering the bus recovery.
+            pub recovery_threshold: u8,
+        }
 
-                #[cfg(feature = "defmt")]
-                {
-                    defmt::debug!("X1: {}", x1);
-                    defmt::debug!("X2: {}", x2);
-                    defmt::debug!("P: {}", p);
+        #[cfg(feature = "i-know-what-i-am-doing")]
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> BMP180<I2C, DELAY, EOC, XCLR, RECOVERY> {
+            /// Split the `BMP180` device into all of its parts, including its pins, retry policy and bus recovery.
+            ///
+            /// The retry counter, the consecutive failures and the statistics are not part of the parts.
+            ///
+            /// Only available when the `i-know-what-i-am-doing` feature is enabled.
+            pub fn into_parts_full(self) -> BMP180Parts<I2C, DELAY, EOC, XCLR, RECOVERY> {
+                BMP180Parts {
+                    addr: self.addr,
+                    mode: self.mode,
+                    calibration: self.calibration,
+                    temperature: self.temperature,
+                    pressure: self.pressure,
+                    raw_temperature: self.raw_temperature,
+                    raw_pressure: self.raw_pressure,
+                    i2c: self.i2c,
+                    delay: self.delay,
+                    eoc: self.eoc,
+                    xclr: self.xclr,
+                    retry_policy: self.retry_policy,
+                    recovery: self.recovery,
+                    recovery_threshold: self.recovery_threshold,
                 }
+            }
 
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("X1: {}", x1);
-                    log::debug!("X2: {}", x2);
-                    log::debug!("P: {}", p);
+            /// Create a `BMP180` device from all of its parts, see [`BMP180::into_parts_full`].
+            ///
+            /// The retry counter, the consecutive failures and the statistics start at zero.
+            ///
+            /// Only available when the `i-know-what-i-am-doing` feature is enabled.
+            pub fn from_parts_full(parts: BMP180Parts<I2C, DELAY, EOC, XCLR, RECOVERY>) -> Self {
+                Self {
+                    addr: parts.addr,
+                    mode: parts.mode,
+                    calibration: parts.calibration,
+                    temperature: parts.temperature,
+                    pressure: parts.pressure,
+                    raw_temperature: parts.raw_temperature,
+                    raw_pressure: parts.raw_pressure,
+                    compensation_failed: false,
+                    i2c: parts.i2c,
+                    delay: parts.delay,
+                    eoc: parts.eoc,
+                    xclr: parts.xclr,
+                    retry_policy: parts.retry_policy,
+                    retries: 0,
+                    recovery: parts.recovery,
+                    recovery_threshold: parts.recovery_threshold,
+                    failures: 0,
+                    #[cfg(feature = "statistics")]
+                    statistics: Statistics::default(),
                 }
-
-                Some(p)
             }
         }
 
@@ -680,6 +1390,8 @@ pub mod module {
 
             /// Create a `BMP180` device from its parts.
             ///
+            /// Uses the default retry policy and no bus recovery.
+            ///
             /// Only available when the `i-know-what-i-am-doing` feature is enabled.
             pub fn from_parts(
                 addr: Address,
@@ -690,19 +1402,546 @@ pub mod module {
                 i2c: I2C,
                 delay: DELAY,
             ) -> Self {
-                Self {
+                Self::from_parts_full(BMP180Parts {
                     addr,
                     mode,
                     calibration,
                     temperature,
                     pressure,
+                    raw_temperature: 0,
+                    raw_pressure: 0,
                     i2c,
                     delay,
-                }
+                    eoc: None,
+                    xclr: None,
+                    retry_policy: RetryPolicy::default(),
+                    recovery: None,
+                    recovery_threshold: u8::MAX,
+                })
             }
         }
     }
 
     #[cfg(feature=feature_)]
     pub use inner::*;
+
+    #[cfg(feature=feature_)]
+    pub use crate::scan::module::scan;
+
+    #[cfg(feature=feature_)]
+    pub use crate::mux::module::MuxedBMP180;
+
+    #[cfg(feature=feature_)]
+    pub use crate::aggregate::module::Aggregator;
+
+    #[cfg(feature=feature_)]
+    pub use crate::recovery::module::BusRecovery;
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use embassy_futures::block_on;
+
+    use std::vec::Vec;
+
+    use crate::{
+        asynch::UninitBMP180,
+        mock::{calibration, mock, State},
+        register::Register,
+        Address, BMP180Error, Calibration, Mode, RetryPolicy,
+    };
+
+    /// Whether the whole calibration block was read.
+    fn read_calibration_block(state: &State) -> bool {
+        state.reads.contains(&(Register::CalibrationAc1 as u8, 22))
+    }
+
+    #[test]
+    fn initialize_with_matching_calibration() {
+        let (i2c, delay, state) = mock();
+
+        let (bmp180, refreshed) = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .build()
+                .initialize_with_calibration(Some(calibration())),
+        )
+        .unwrap();
+
+        assert!(!refreshed);
+        assert_eq!(bmp180.calibration(), &calibration());
+
+        // Only the first and the last calibration words are spot-checked.
+        let state = state.borrow();
+
+        assert!(!read_calibration_block(&state));
+        assert!(state.reads.contains(&(Register::CalibrationAc1 as u8, 2)));
+        assert!(state.reads.contains(&(Register::CalibrationMd as u8, 2)));
+    }
+
+    #[test]
+    fn initialize_with_stale_calibration() {
+        let (i2c, delay, state) = mock();
+
+        // Differs from the device in the last calibration word only.
+        let stale = Calibration {
+            md: 2869,
+            ..calibration()
+        };
+
+        let (bmp180, refreshed) = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .build()
+                .initialize_with_calibration(Some(stale)),
+        )
+        .unwrap();
+
+        assert!(refreshed);
+        assert_eq!(bmp180.calibration(), &calibration());
+        assert!(read_calibration_block(&state.borrow()));
+    }
+
+    #[test]
+    fn initialize_without_calibration() {
+        let (i2c, delay, _) = mock();
+
+        let (bmp180, refreshed) = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .build()
+                .initialize_with_calibration(None),
+        )
+        .unwrap();
+
+        assert!(refreshed);
+        assert_eq!(bmp180.calibration(), &calibration());
+    }
+
+    /// `XCLR` pin recording its levels, failing if `fail` is set.
+    struct XclrPin {
+        levels: Vec<bool>,
+        fail: bool,
+    }
+
+    impl embedded_hal::digital::ErrorType for XclrPin {
+        type Error = embedded_hal::digital::ErrorKind;
+    }
+
+    impl embedded_hal::digital::OutputPin for XclrPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.set_state(false)
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.set_state(true)
+        }
+    }
+
+    impl XclrPin {
+        fn set_state(&mut self, high: bool) -> Result<(), embedded_hal::digital::ErrorKind> {
+            if self.fail {
+                return Err(embedded_hal::digital::ErrorKind::Other);
+            }
+
+            self.levels.push(high);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn builder_keeps_settings_when_adding_pins() {
+        let (i2c, delay, state) = mock();
+
+        let mut xclr = XclrPin {
+            levels: Vec::new(),
+            fail: false,
+        };
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .addr(Address::Other(0x76))
+                .mode(Mode::UltraLowPower)
+                .xclr(&mut xclr)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        assert_eq!(bmp180.mode(), Mode::UltraLowPower);
+        assert_eq!(bmp180.pressure(), 69964);
+        assert!(state
+            .borrow()
+            .writes
+            .iter()
+            .all(|(address, _)| *address == 0x76));
+
+        block_on(bmp180.reset()).unwrap();
+
+        drop(bmp180);
+
+        assert_eq!(xclr.levels, [false, true, false, true]);
+    }
+
+    #[test]
+    fn xclr_error_is_carried() {
+        let (i2c, delay, _) = mock();
+
+        let xclr = XclrPin {
+            levels: Vec::new(),
+            fail: true,
+        };
+
+        let result = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .xclr(xclr)
+                .build()
+                .initialize(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(BMP180Error::Pin(embedded_hal::digital::ErrorKind::Other))
+        ));
+    }
+
+    #[test]
+    fn self_test_passes() {
+        let (i2c, delay, state) = mock();
+
+        state.borrow_mut().noise = true;
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .mode(Mode::Standard)
+                .retry_policy(RetryPolicy::new(2))
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        let (temperature, pressure) = (bmp180.temperature(), bmp180.pressure());
+        let (raw_temperature, raw_pressure) = (bmp180.raw_temperature(), bmp180.raw_pressure());
+        #[cfg(feature = "statistics")]
+        let transactions = bmp180.statistics().transactions();
+
+        state.borrow_mut().failures = 1;
+
+        let report = block_on(bmp180.self_test()).unwrap();
+
+        assert!(report.passed());
+        assert_eq!(report.id, 0x55);
+        assert!(report.raw_temperature_changes);
+        assert!(report.raw_pressure_changes);
+        assert!(report.pressure_timing.iter().all(|timing| *timing));
+        assert!(report.modes_consistent);
+        assert!(report
+            .mode_pressures
+            .iter()
+            .all(|pressure| pressure.abs_diff(69964) <= 10));
+
+        // The self-test leaves the readings, the mode and the counters untouched.
+        assert_eq!(bmp180.mode(), Mode::Standard);
+        assert_eq!(
+            (bmp180.temperature(), bmp180.pressure()),
+            (temperature, pressure)
+        );
+        assert_eq!(
+            (bmp180.raw_temperature(), bmp180.raw_pressure()),
+            (raw_temperature, raw_pressure)
+        );
+        assert_eq!(bmp180.retries(), 0);
+        #[cfg(feature = "statistics")]
+        assert_eq!(bmp180.statistics().transactions(), transactions);
+    }
+
+    #[test]
+    fn self_test_fails() {
+        let (i2c, delay, state) = mock();
+
+        let mut bmp180 = block_on(UninitBMP180::builder(i2c, delay).build().initialize()).unwrap();
+
+        // Without noise, neither raw value changes.
+        let report = block_on(bmp180.self_test()).unwrap();
+
+        assert!(report.id_valid);
+        assert!(!report.raw_temperature_changes);
+        assert!(!report.raw_pressure_changes);
+        assert!(!report.passed());
+
+        state.borrow_mut().noise = true;
+        state.borrow_mut().stuck_conversion = true;
+
+        let report = block_on(bmp180.self_test()).unwrap();
+
+        assert!(!report.temperature_timing);
+        assert!(report.pressure_timing.iter().all(|timing| !*timing));
+        assert!(!report.passed());
+
+        state.borrow_mut().stuck_conversion = false;
+        state.borrow_mut().id = 0x42;
+
+        let report = block_on(bmp180.self_test()).unwrap();
+
+        assert_eq!(report.id, 0x42);
+        assert!(!report.id_valid);
+        assert!(!report.passed());
+    }
+
+    #[cfg(not(feature = "disable-arithmetic-checks"))]
+    #[test]
+    fn to_record_carries_flags() {
+        use crate::{
+            health::{HealthIssue, HealthMonitor},
+            record::Flags,
+        };
+
+        let (i2c, delay, state) = mock();
+
+        // `X1 + MD` is zero, the temperature compensation divides by zero.
+        state.borrow_mut().raw_temperature = 20285;
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .mode(Mode::UltraLowPower)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            block_on(bmp180.update()),
+            Err(BMP180Error::Arithmetic(_))
+        ));
+
+        let mut monitor = HealthMonitor::default();
+        let health = bmp180.check_health(&mut monitor);
+        let record = bmp180.to_record(42, health);
+
+        assert!(health.has(HealthIssue::PressureOutOfRange));
+        assert_eq!(record.timestamp, 42);
+        assert_eq!(record.raw_temperature, 20285);
+        assert_eq!(record.raw_pressure, 23843);
+        assert_eq!(
+            record.flags,
+            Flags::from_health(health) | Flags::COMPENSATION_FAILED
+        );
+
+        // A successful update clears the compensation failure.
+        state.borrow_mut().raw_temperature = 27898;
+
+        block_on(bmp180.update()).unwrap();
+
+        let health = bmp180.check_health(&mut HealthMonitor::default());
+
+        assert!(health.is_healthy());
+        assert_eq!(bmp180.to_record(43, health).flags, Flags::NONE);
+    }
+
+    #[cfg(feature = "statistics")]
+    #[test]
+    fn statistics_include_initialization() {
+        let (i2c, delay, state) = mock();
+
+        state.borrow_mut().failures = 1;
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .retry_policy(RetryPolicy::new(2))
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        let statistics = bmp180.statistics();
+
+        // Reading the ID twice and the calibration data once,
+        // with 2 address bytes, 1 register byte, 3 conditions and the read bytes each.
+        assert_eq!(bmp180.retries(), 1);
+        assert_eq!(statistics.transactions(), 3);
+        assert_eq!(statistics.retries(), 1);
+        assert_eq!(statistics.bus_bits(), 2 * (5 * 9 + 3) + (25 * 9 + 3));
+        assert_eq!(statistics.bus_time_us(100_000), 3240);
+        assert_eq!(statistics.updates(), 0);
+
+        bmp180.reset_statistics();
+
+        assert_eq!(bmp180.statistics().retries(), 0);
+        assert_eq!(bmp180.retries(), 1);
+    }
+
+    #[cfg(feature = "statistics")]
+    #[test]
+    fn statistics_record_recovery_errors() {
+        use crate::{recovery::asynch::BusRecovery, statistics::RecordedError};
+
+        /// Recovery leaving the bus as is.
+        struct Recovery;
+
+        impl<I2C> BusRecovery<I2C> for Recovery {
+            async fn recover(&mut self, _i2c: &mut I2C) {}
+        }
+
+        let (i2c, delay, state) = mock();
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .bus_recovery(Recovery, 1)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        state.borrow_mut().id = 0x42;
+
+        assert!(matches!(
+            block_on(bmp180.recover_bus()),
+            Err(BMP180Error::InvalidId(0x42))
+        ));
+
+        let statistics = bmp180.statistics();
+
+        assert_eq!(statistics.other_errors(), 1);
+        assert_eq!(
+            statistics.last_error(),
+            Some(RecordedError::InvalidId(0x42))
+        );
+        assert_eq!(statistics.updates(), 0);
+    }
+
+    #[cfg(feature = "i-know-what-i-am-doing")]
+    #[test]
+    fn parts_round_trip_keeps_settings() {
+        use crate::asynch::BMP180;
+
+        let (i2c, delay, _) = mock();
+
+        let xclr = XclrPin {
+            levels: Vec::new(),
+            fail: false,
+        };
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .mode(Mode::UltraLowPower)
+                .xclr(xclr)
+                .retry_policy(RetryPolicy::new(3))
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        let parts = bmp180.into_parts_full();
+
+        assert_eq!(parts.raw_temperature, 27898);
+        assert_eq!(parts.xclr.as_ref().unwrap().levels, [false, true]);
+
+        let mut bmp180 = BMP180::from_parts_full(parts);
+
+        assert_eq!(bmp180.retry_policy(), &RetryPolicy::new(3));
+        assert_eq!(bmp180.mode(), Mode::UltraLowPower);
+        assert_eq!(bmp180.pressure(), 69964);
+
+        block_on(bmp180.update()).unwrap();
+
+        assert_eq!(bmp180.pressure(), 69964);
+    }
+
+    #[cfg(feature = "i-know-what-i-am-doing")]
+    #[test]
+    fn parts_tuple_round_trip() {
+        use crate::asynch::BMP180;
+
+        let (i2c, delay, _) = mock();
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .addr(Address::Other(0x76))
+                .mode(Mode::UltraLowPower)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        let (addr, mode, calibration, temperature, pressure, i2c, delay) = bmp180.into_parts();
+
+        assert_eq!(u8::from(addr), 0x76);
+        assert_eq!((temperature, pressure), (150, 69964));
+
+        let mut bmp180 =
+            BMP180::from_parts(addr, mode, calibration, temperature, pressure, i2c, delay);
+
+        assert_eq!(bmp180.mode(), Mode::UltraLowPower);
+        assert_eq!(bmp180.temperature(), 150);
+        assert_eq!(bmp180.retry_policy(), &RetryPolicy::default());
+
+        block_on(bmp180.update()).unwrap();
+
+        assert_eq!(bmp180.pressure(), 69964);
+    }
+
+    #[cfg(feature = "embedded-storage")]
+    mod storage {
+        use core::convert::Infallible;
+
+        use embedded_storage::{ReadStorage, Storage};
+
+        use super::*;
+
+        /// RAM backed storage.
+        struct Ram([u8; 64]);
+
+        impl ReadStorage for Ram {
+            type Error = Infallible;
+
+            fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+                let offset = offset as usize;
+
+                bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+
+                Ok(())
+            }
+
+            fn capacity(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl Storage for Ram {
+            fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+                let offset = offset as usize;
+
+                self.0[offset..offset + bytes.len()].copy_from_slice(bytes);
+
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn initialize_with_storage_refreshes_record() {
+            let mut ram = Ram([0xFF; 64]);
+
+            for expected in [true, false] {
+                let (i2c, delay, state) = mock();
+
+                let (bmp180, refreshed) = block_on(
+                    UninitBMP180::builder(i2c, delay)
+                        .build()
+                        .initialize_with_storage(&mut ram, 8),
+                )
+                .unwrap();
+
+                assert_eq!(refreshed, expected);
+                assert_eq!(read_calibration_block(&state.borrow()), expected);
+                assert_eq!(bmp180.calibration(), &calibration());
+                assert_eq!(ram.0[8..32], calibration().to_record());
+            }
+        }
+    }
 }
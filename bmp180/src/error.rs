@@ -1,13 +1,266 @@
 //! Error types for `BMP180` devices.
 
+use core::fmt;
+
+/// I2C operation that failed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum Operation {
+    /// Reading the device ID.
+    ReadId,
+    /// Reading the calibration data.
+    ReadCalibration,
+    /// Starting a temperature conversion.
+    StartTemperature,
+    /// Reading the raw temperature.
+    ReadTemperature,
+    /// Starting a pressure conversion.
+    StartPressure,
+    /// Reading the raw pressure.
+    ReadPressure,
+    /// Reading the conversion status.
+    ReadStatus,
+}
+
+impl Operation {
+    /// All operations.
+    pub const ALL: [Operation; 7] = [
+        Operation::ReadId,
+        Operation::ReadCalibration,
+        Operation::StartTemperature,
+        Operation::ReadTemperature,
+        Operation::StartPressure,
+        Operation::ReadPressure,
+        Operation::ReadStatus,
+    ];
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            Operation::ReadId => "reading the device ID",
+            Operation::ReadCalibration => "reading the calibration data",
+            Operation::StartTemperature => "starting a temperature conversion",
+            Operation::ReadTemperature => "reading the raw temperature",
+            Operation::StartPressure => "starting a pressure conversion",
+            Operation::ReadPressure => "reading the raw pressure",
+            Operation::ReadStatus => "reading the conversion status",
+        };
+
+        f.write_str(operation)
+    }
+}
+
+/// Intermediate value of the compensation that could not be computed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum Stage {
+    /// `B5`, shared by the temperature and pressure compensation.
+    B5,
+    /// True temperature.
+    Temperature,
+    /// `B6`.
+    B6,
+    /// `B3`, including its `X1`, `X2` and `X3` terms.
+    B3,
+    /// `B4`, including its `X1`, `X2` and `X3` terms.
+    B4,
+    /// `B7`.
+    B7,
+    /// True pressure, including its final `X1` and `X2` terms.
+    Pressure,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            Stage::B5 => "B5",
+            Stage::Temperature => "T",
+            Stage::B6 => "B6",
+            Stage::B3 => "B3",
+            Stage::B4 => "B4",
+            Stage::B7 => "B7",
+            Stage::Pressure => "P",
+        };
+
+        f.write_str(stage)
+    }
+}
+
+/// Reason of an arithmetic error.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum ArithmeticReason {
+    /// An operation overflowed.
+    Overflow,
+    /// A divisor was zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for ArithmeticReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticReason::Overflow => f.write_str("overflow"),
+            ArithmeticReason::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+/// Arithmetic error of the compensation.
+///
+/// Carries the raw readings, so corrupt readings or calibration data can be diagnosed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct ArithmeticError {
+    /// Intermediate value that could not be computed.
+    pub stage: Stage,
+    /// Reason of the error.
+    pub reason: ArithmeticReason,
+    /// Raw temperature (`UT`).
+    pub raw_temperature: i16,
+    /// Raw pressure (`UP`), `None` if only the temperature was computed.
+    pub raw_pressure: Option<i32>,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} computing {} (UT = {}",
+            self.reason, self.stage, self.raw_temperature
+        )?;
+
+        if let Some(raw_pressure) = self.raw_pressure {
+            write!(f, ", UP = {raw_pressure}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
 /// Error type for `BMP180` devices.
 #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
 #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
 pub enum BMP180Error<I2CError> {
     /// I2C error.
-    I2C(I2CError),
+    I2C {
+        /// Operation that failed.
+        operation: Operation,
+        /// I2C error.
+        error: I2CError,
+    },
     /// Invalid device ID.
     InvalidId(u8),
     /// Arithmetic error, like deviding by zero, overflow, etc.
-    Arithmetic,
+    Arithmetic(ArithmeticError),
+    /// `EOC` or `XCLR` pin error.
+    Pin(embedded_hal::digital::ErrorKind),
+}
+
+impl<I2CError> BMP180Error<I2CError> {
+    /// Create an I2C error mapper for the given operation.
+    pub(crate) fn i2c(operation: Operation) -> impl FnOnce(I2CError) -> Self {
+        move |error| BMP180Error::I2C { operation, error }
+    }
+
+    /// Create a pin error from the error of the `EOC` or `XCLR` pin.
+    pub(crate) fn pin<P: embedded_hal::digital::Error>(error: P) -> Self {
+        BMP180Error::Pin(error.kind())
+    }
+
+    /// Operation that failed, if the error is an I2C error.
+    pub fn operation(&self) -> Option<Operation> {
+        match self {
+            BMP180Error::I2C { operation, .. } => Some(*operation),
+            _ => None,
+        }
+    }
+
+    /// Kind of the I2C error, if the error is an I2C error.
+    ///
+    /// Allows distinguishing e.g. a missing acknowledge from an arbitration loss without knowing the HAL.
+    pub fn i2c_kind(&self) -> Option<embedded_hal::i2c::ErrorKind>
+    where
+        I2CError: embedded_hal::i2c::Error,
+    {
+        match self {
+            BMP180Error::I2C { error, .. } => Some(error.kind()),
+            _ => None,
+        }
+    }
+}
+
+impl<I2CError: fmt::Debug> fmt::Display for BMP180Error<I2CError> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BMP180Error::I2C { operation, error } => {
+                write!(f, "I2C error while {operation}: {error:?}")
+            }
+            BMP180Error::InvalidId(id) => write!(f, "invalid device ID: {id:#04x}"),
+            BMP180Error::Arithmetic(error) => write!(f, "arithmetic error: {error}"),
+            BMP180Error::Pin(kind) => write!(f, "pin error: {kind}"),
+        }
+    }
+}
+
+/// Only available when the `impl-debug` feature is enabled.
+#[cfg(feature = "impl-debug")]
+impl<I2CError: fmt::Debug> core::error::Error for BMP180Error<I2CError> {}
+
+/// Error initializing a `BMP180` device with calibration data persisted in a storage.
+///
+/// Only available when the `embedded-storage` feature is enabled.
+#[cfg(feature = "embedded-storage")]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum StorageInitError<I2CError, StorageError> {
+    /// Device error.
+    Device(BMP180Error<I2CError>),
+    /// Storage error while loading or storing the calibration record.
+    Storage(StorageError),
+}
+
+#[cfg(feature = "embedded-storage")]
+impl<I2CError: fmt::Debug, StorageError: fmt::Debug> fmt::Display
+    for StorageInitError<I2CError, StorageError>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageInitError::Device(error) => write!(f, "{error}"),
+            StorageInitError::Storage(error) => write!(f, "storage error: {error:?}"),
+        }
+    }
+}
+
+/// Only available when the `impl-debug` and `embedded-storage` features are enabled.
+#[cfg(all(feature = "impl-debug", feature = "embedded-storage"))]
+impl<I2CError: fmt::Debug, StorageError: fmt::Debug> core::error::Error
+    for StorageInitError<I2CError, StorageError>
+{
 }
+
+/// Error returned when creating [`Calibration`](crate::Calibration) from a slice with an invalid length.
+///
+/// Contains the length of the slice, calibration data is exactly 22 bytes long.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct CalibrationLengthError(pub usize);
+
+impl fmt::Display for CalibrationLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid calibration data length: {}, expected 22 bytes",
+            self.0
+        )
+    }
+}
+
+/// Only available when the `impl-debug` feature is enabled.
+#[cfg(feature = "impl-debug")]
+impl core::error::Error for CalibrationLengthError {}
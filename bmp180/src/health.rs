@@ -0,0 +1,506 @@
+//! Sensor health monitoring and plausibility checks.
+//!
+//! A dying sensor may return implausible readings without any I2C or arithmetic error.
+//! The monitor is fed with successive readings, e.g. using `BMP180::check_health`
+//! after each `BMP180::update`, from either the `blocking` or the `asynch` device,
+//! and flags out-of-spec values, implausible rates of change, stuck raw values and repeated identical outputs.
+
+use core::fmt;
+
+/// Health issue of a reading.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum HealthIssue {
+    /// Pressure outside of the operating range.
+    PressureOutOfRange = 0,
+    /// Temperature outside of the operating range.
+    TemperatureOutOfRange = 1,
+    /// Pressure changed faster than the maximum step between two readings.
+    PressureRate = 2,
+    /// Temperature changed faster than the maximum step between two readings.
+    TemperatureRate = 3,
+    /// Raw temperature (`UT`) did not change over the stuck threshold.
+    StuckRawTemperature = 4,
+    /// Raw pressure (`UP`) did not change over the stuck threshold.
+    StuckRawPressure = 5,
+    /// Temperature and pressure did not change over the stuck threshold.
+    RepeatedOutput = 6,
+}
+
+impl HealthIssue {
+    /// All issues.
+    pub const ALL: [HealthIssue; 7] = [
+        HealthIssue::PressureOutOfRange,
+        HealthIssue::TemperatureOutOfRange,
+        HealthIssue::PressureRate,
+        HealthIssue::TemperatureRate,
+        HealthIssue::StuckRawTemperature,
+        HealthIssue::StuckRawPressure,
+        HealthIssue::RepeatedOutput,
+    ];
+
+    /// Bit of the issue in [`HealthStatus`].
+    fn mask(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let issue = match self {
+            HealthIssue::PressureOutOfRange => "pressure out of range",
+            HealthIssue::TemperatureOutOfRange => "temperature out of range",
+            HealthIssue::PressureRate => "implausible pressure rate of change",
+            HealthIssue::TemperatureRate => "implausible temperature rate of change",
+            HealthIssue::StuckRawTemperature => "stuck raw temperature",
+            HealthIssue::StuckRawPressure => "stuck raw pressure",
+            HealthIssue::RepeatedOutput => "repeated identical output",
+        };
+
+        f.write_str(issue)
+    }
+}
+
+/// Health status of a reading.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct HealthStatus {
+    /// Bit set of [`HealthIssue`]s.
+    issues: u8,
+}
+
+impl HealthStatus {
+    /// Whether no issue was detected.
+    pub fn is_healthy(&self) -> bool {
+        self.issues == 0
+    }
+
+    /// Whether the given issue was detected.
+    pub fn has(&self, issue: HealthIssue) -> bool {
+        self.issues & issue.mask() != 0
+    }
+
+    /// Detected issues.
+    pub fn issues(&self) -> impl Iterator<Item = HealthIssue> + '_ {
+        HealthIssue::ALL
+            .into_iter()
+            .filter(move |issue| self.has(*issue))
+    }
+
+    /// Create a status from its bit set.
+    pub(crate) fn from_bits(issues: u8) -> Self {
+        Self { issues }
+    }
+
+    /// Bit set of the detected issues.
+    pub(crate) fn bits(&self) -> u8 {
+        self.issues
+    }
+
+    /// Flag the given issue if `condition` is `true`.
+    fn flag(&mut self, issue: HealthIssue, condition: bool) {
+        if condition {
+            self.issues |= issue.mask();
+        }
+    }
+}
+
+/// Plausibility limits.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limits {
+    /// Minimum pressure in `Pa`.
+    pub min_pressure: i32,
+    /// Maximum pressure in `Pa`.
+    pub max_pressure: i32,
+    /// Minimum temperature in `0.1 C`.
+    pub min_temperature: i32,
+    /// Maximum temperature in `0.1 C`.
+    pub max_temperature: i32,
+    /// Maximum pressure change in `Pa` between two readings.
+    pub max_pressure_step: i32,
+    /// Maximum temperature change in `0.1 C` between two readings.
+    pub max_temperature_step: i32,
+    /// Number of consecutive identical values after which a value is considered stuck.
+    pub stuck_readings: u16,
+    /// Number of consecutive identical raw temperatures after which the raw temperature is considered stuck.
+    ///
+    /// The raw temperature may legitimately stay the same for a long time in a stable environment,
+    /// so [`HealthIssue::StuckRawTemperature`] is only checked if set.
+    pub stuck_raw_temperature_readings: Option<u16>,
+}
+
+impl Default for Limits {
+    /// Operating range of the `BMP180` (`300 hPa` to `1100 hPa`, `-40 C` to `85 C`),
+    /// steps of `5 hPa` and `5 C` and `16` identical readings.
+    ///
+    /// The raw temperature is not checked for being stuck.
+    fn default() -> Self {
+        Self {
+            min_pressure: 30000,
+            max_pressure: 110000,
+            min_temperature: -400,
+            max_temperature: 850,
+            max_pressure_step: 500,
+            max_temperature_step: 50,
+            stuck_readings: 16,
+            stuck_raw_temperature_readings: None,
+        }
+    }
+}
+
+/// Reading checked by the [`HealthMonitor`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reading {
+    /// True temperature in `0.1 C`.
+    pub temperature: i32,
+    /// True pressure in `Pa`.
+    pub pressure: i32,
+    /// Raw temperature (`UT`).
+    pub raw_temperature: i16,
+    /// Raw pressure (`UP`).
+    pub raw_pressure: i32,
+}
+
+/// Sensor health monitor.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct HealthMonitor {
+    /// Plausibility limits.
+    limits: Limits,
+    /// Previous reading.
+    previous: Option<Reading>,
+    /// Number of consecutive readings with the same raw temperature.
+    same_raw_temperature: u16,
+    /// Number of consecutive readings with the same raw pressure.
+    same_raw_pressure: u16,
+    /// Number of consecutive readings with the same temperature and pressure.
+    same_output: u16,
+    /// Status of the last reading.
+    status: HealthStatus,
+}
+
+impl HealthMonitor {
+    /// Create a new monitor with the given limits.
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            previous: None,
+            same_raw_temperature: 0,
+            same_raw_pressure: 0,
+            same_output: 0,
+            status: HealthStatus::default(),
+        }
+    }
+
+    /// Plausibility limits.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Status of the last reading.
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    /// Clear the history and the status.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.same_raw_temperature = 0;
+        self.same_raw_pressure = 0;
+        self.same_output = 0;
+        self.status = HealthStatus::default();
+    }
+
+    /// Check a new reading.
+    pub fn check(&mut self, reading: Reading) -> HealthStatus {
+        let limits = &self.limits;
+        let mut status = HealthStatus::default();
+
+        status.flag(
+            HealthIssue::PressureOutOfRange,
+            !(limits.min_pressure..=limits.max_pressure).contains(&reading.pressure),
+        );
+        status.flag(
+            HealthIssue::TemperatureOutOfRange,
+            !(limits.min_temperature..=limits.max_temperature).contains(&reading.temperature),
+        );
+
+        match self.previous {
+            Some(previous) => {
+                let pressure_step = reading.pressure.abs_diff(previous.pressure);
+                let temperature_step = reading.temperature.abs_diff(previous.temperature);
+
+                status.flag(
+                    HealthIssue::PressureRate,
+                    pressure_step > limits.max_pressure_step.unsigned_abs(),
+                );
+                status.flag(
+                    HealthIssue::TemperatureRate,
+                    temperature_step > limits.max_temperature_step.unsigned_abs(),
+                );
+
+                self.same_raw_temperature = Self::count(
+                    self.same_raw_temperature,
+                    reading.raw_temperature == previous.raw_temperature,
+                );
+                self.same_raw_pressure = Self::count(
+                    self.same_raw_pressure,
+                    reading.raw_pressure == previous.raw_pressure,
+                );
+                self.same_output = Self::count(
+                    self.same_output,
+                    reading.temperature == previous.temperature
+                        && reading.pressure == previous.pressure,
+                );
+            }
+            None => {
+                self.same_raw_temperature = 1;
+                self.same_raw_pressure = 1;
+                self.same_output = 1;
+            }
+        }
+
+        status.flag(
+            HealthIssue::StuckRawTemperature,
+            limits
+                .stuck_raw_temperature_readings
+                .is_some_and(|stuck_readings| self.same_raw_temperature >= stuck_readings),
+        );
+        status.flag(
+            HealthIssue::StuckRawPressure,
+            self.same_raw_pressure >= limits.stuck_readings,
+        );
+        status.flag(
+            HealthIssue::RepeatedOutput,
+            self.same_output >= limits.stuck_readings,
+        );
+
+        self.previous = Some(reading);
+        self.status = status;
+
+        status
+    }
+
+    /// Count consecutive identical values.
+    fn count(count: u16, same: bool) -> u16 {
+        if same {
+            count.saturating_add(1)
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        Self::new(Limits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Datasheet example reading.
+    const READING: Reading = Reading {
+        temperature: 150,
+        pressure: 69964,
+        raw_temperature: 27898,
+        raw_pressure: 23843,
+    };
+
+    /// Reading with the raw values shifted by `offset`.
+    fn noisy(offset: i16) -> Reading {
+        Reading {
+            raw_temperature: READING.raw_temperature + offset,
+            raw_pressure: READING.raw_pressure + offset as i32,
+            pressure: READING.pressure + offset as i32,
+            ..READING
+        }
+    }
+
+    /// Issues of the last reading after checking all `readings`.
+    fn issues(limits: Limits, readings: &[Reading]) -> HealthStatus {
+        let mut monitor = HealthMonitor::new(limits);
+
+        readings.iter().fold(HealthStatus::default(), |_, reading| {
+            monitor.check(*reading)
+        })
+    }
+
+    #[test]
+    fn healthy() {
+        let status = issues(Limits::default(), &[noisy(0), noisy(1), noisy(0)]);
+
+        assert!(status.is_healthy());
+        assert_eq!(status.issues().count(), 0);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let status = issues(
+            Limits::default(),
+            &[Reading {
+                pressure: 29999,
+                temperature: 851,
+                ..READING
+            }],
+        );
+
+        assert!(status.has(HealthIssue::PressureOutOfRange));
+        assert!(status.has(HealthIssue::TemperatureOutOfRange));
+        assert_eq!(status.issues().count(), 2);
+
+        let status = issues(
+            Limits::default(),
+            &[Reading {
+                pressure: 110000,
+                temperature: -400,
+                ..READING
+            }],
+        );
+
+        assert!(status.is_healthy());
+    }
+
+    #[test]
+    fn rates() {
+        let next = Reading {
+            pressure: READING.pressure + 501,
+            temperature: READING.temperature - 51,
+            ..noisy(1)
+        };
+
+        let status = issues(Limits::default(), &[READING, next]);
+
+        assert!(status.has(HealthIssue::PressureRate));
+        assert!(status.has(HealthIssue::TemperatureRate));
+        assert_eq!(status.issues().count(), 2);
+
+        let next = Reading {
+            pressure: READING.pressure + 500,
+            temperature: READING.temperature - 50,
+            ..noisy(1)
+        };
+
+        assert!(issues(Limits::default(), &[READING, next]).is_healthy());
+    }
+
+    #[test]
+    fn stable_raw_temperature_is_healthy_by_default() {
+        let readings: [Reading; 64] = core::array::from_fn(|i| Reading {
+            raw_temperature: READING.raw_temperature,
+            ..noisy(i as i16 % 2)
+        });
+
+        assert!(issues(Limits::default(), &readings).is_healthy());
+    }
+
+    #[test]
+    fn stuck_raw_temperature() {
+        let limits = Limits {
+            stuck_raw_temperature_readings: Some(4),
+            ..Limits::default()
+        };
+
+        let readings: [Reading; 4] = core::array::from_fn(|i| Reading {
+            raw_temperature: READING.raw_temperature,
+            ..noisy(i as i16 % 2)
+        });
+
+        assert!(issues(limits, &readings[..3]).is_healthy());
+
+        let status = issues(limits, &readings);
+
+        assert!(status.has(HealthIssue::StuckRawTemperature));
+        assert_eq!(status.issues().count(), 1);
+    }
+
+    #[test]
+    fn stuck_raw_pressure() {
+        let limits = Limits {
+            stuck_readings: 4,
+            ..Limits::default()
+        };
+
+        let readings: [Reading; 4] = core::array::from_fn(|i| Reading {
+            raw_pressure: READING.raw_pressure,
+            ..noisy(i as i16 % 2)
+        });
+
+        assert!(issues(limits, &readings[..3]).is_healthy());
+
+        let status = issues(limits, &readings);
+
+        assert!(status.has(HealthIssue::StuckRawPressure));
+        assert_eq!(status.issues().count(), 1);
+    }
+
+    #[test]
+    fn repeated_output() {
+        let limits = Limits {
+            stuck_readings: 4,
+            ..Limits::default()
+        };
+
+        let readings: [Reading; 4] = core::array::from_fn(|i| Reading {
+            temperature: READING.temperature,
+            pressure: READING.pressure,
+            ..noisy(i as i16 % 2)
+        });
+
+        assert!(issues(limits, &readings[..3]).is_healthy());
+
+        let status = issues(limits, &readings);
+
+        assert!(status.has(HealthIssue::RepeatedOutput));
+        assert_eq!(status.issues().count(), 1);
+    }
+
+    #[test]
+    fn count_resets_on_change() {
+        let limits = Limits {
+            stuck_readings: 3,
+            ..Limits::default()
+        };
+
+        let mut monitor = HealthMonitor::new(limits);
+
+        // Two identical readings, a change, then two identical readings again.
+        for reading in [READING, READING, noisy(1), noisy(1)] {
+            assert!(monitor.check(reading).is_healthy());
+        }
+
+        let status = monitor.check(noisy(1));
+
+        assert!(status.has(HealthIssue::StuckRawPressure));
+        assert!(status.has(HealthIssue::RepeatedOutput));
+        assert_eq!(monitor.status(), status);
+
+        monitor.reset();
+
+        assert!(monitor.status().is_healthy());
+        assert!(monitor.check(noisy(1)).is_healthy());
+    }
+
+    #[test]
+    fn status_bits() {
+        for issue in HealthIssue::ALL {
+            let status = HealthStatus::from_bits(issue.mask());
+
+            assert!(status.has(issue));
+            assert_eq!(status.issues().collect::<std::vec::Vec<_>>(), [issue]);
+            assert_eq!(HealthStatus::from_bits(status.bits()), status);
+        }
+    }
+}
@@ -9,32 +9,100 @@
 //! - `impl-debug`: implements `core::fmt::Debug` for structs and enums.
 //! - `impl-defmt-format`: implements `defmt::Format` for structs and enums.
 //! - `fuzz`: enables the `fuzz` module for fuzz testing.
+//! - `replay`: enables the `replay` module to play back recorded I2C traces in regression tests. Implies `trace`.
+//! - `serde`: implements `serde::Serialize` and `serde::Deserialize` for calibration data, settings and readings.
+//! - `embedded-storage`: enables persisting calibration data using the [`embedded-storage`](https://crates.io/crates/embedded-storage) traits.
+//! - `linux-cli`: builds the `bmp180` command line tool using [`linux-embedded-hal`](https://crates.io/crates/linux-embedded-hal).
+//! - `statistics`: enables per-device statistics, like successful updates, errors, transactions and estimated bus time.
+//! - `std`: enables the `rawlog` module to decode raw sample logs and data logger records on the host.
+//! - `trace`: enables the `trace` module to record I2C transactions, e.g. to reproduce bugs from the field.
+//! - `uom`: enables accessors returning typed physical quantities from the [`uom`](https://crates.io/crates/uom) crate.
 //! - `disable-arithmetic-checks`: disables arithmetic checks.
 //! - `i-know-what-i-am-doing`: allows you to split an initialized device into its parts and put it back together.
-//!     Useful when you want to release the I2C bus and use it for something else.
-//!     This is not recommended though, you can use [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus)
-//!     or [`embassy-embedded-hal`](https://crates.io/crates/embassy-embedded-hal) to share the I2C bus.
+//!   Useful when you want to release the I2C bus and use it for something else.
+//!   This is not recommended though, you can use [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus)
+//!   or [`embassy-embedded-hal`](https://crates.io/crates/embassy-embedded-hal) to share the I2C bus.
 
 #![no_std]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
 mod address;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod aggregate;
 mod calibration;
+#[cfg(any(feature = "async", feature = "blocking", feature = "std"))]
+mod compensation;
+mod crc;
 mod device;
 mod error;
 mod id;
+#[cfg(all(test, any(feature = "async", feature = "blocking")))]
+mod mock;
 mod mode;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod mux;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod pin;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod recovery;
 mod register;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod retry;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod scan;
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod selftest;
+#[cfg(all(test, feature = "serde", feature = "impl-debug"))]
+mod serde_tests;
+#[cfg(all(feature = "statistics", any(feature = "async", feature = "blocking")))]
+mod statistics;
 
 #[cfg(feature = "fuzz")]
 pub mod fuzz;
 
+pub mod health;
+#[cfg(feature = "std")]
+pub mod rawlog;
+pub mod record;
+#[cfg(all(feature = "replay", any(feature = "async", feature = "blocking")))]
+pub mod replay;
+pub mod sensor;
+pub mod storm;
+#[cfg(all(feature = "trace", any(feature = "async", feature = "blocking")))]
+pub mod trace;
+pub mod units;
+pub mod zambretti;
+
 pub use crate::address::Address;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::aggregate::FusedReading;
 pub use crate::calibration::Calibration;
-pub use crate::error::BMP180Error;
+#[cfg(feature = "embedded-storage")]
+pub use crate::error::StorageInitError;
+pub use crate::error::{
+    ArithmeticError, ArithmeticReason, BMP180Error, CalibrationLengthError, Operation, Stage,
+};
 pub use crate::id::Id;
 pub use crate::mode::Mode;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::mux::{MuxChannel, Tca9548a, Tca9548aChannel};
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::pin::NoPin;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::recovery::NoRecovery;
+pub use crate::register::Register;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::retry::{Backoff, RetryOn, RetryPolicy};
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::scan::{Probe, ScanReport};
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use crate::selftest::SelfTestReport;
+#[cfg(all(feature = "statistics", any(feature = "async", feature = "blocking")))]
+pub use crate::statistics::{RecordedError, Statistics};
 
 #[cfg(feature = "async")]
 pub use crate::device::asynch;
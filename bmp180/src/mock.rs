@@ -0,0 +1,320 @@
+//! Simulated `BMP180` device for unit tests.
+
+use core::cell::RefCell;
+
+use std::{rc::Rc, vec::Vec};
+
+use duplicate::duplicate_item;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+
+use crate::{
+    calibration::Calibration,
+    register::{Register, SCO},
+};
+
+/// Conversion time in nanoseconds, shorter than the maximum conversion time of every mode.
+const CONVERSION_NS: u32 = 3_000_000;
+
+/// Calibration data of the datasheet example.
+pub(crate) fn calibration() -> Calibration {
+    Calibration {
+        ac1: 408,
+        ac2: -72,
+        ac3: -14383,
+        ac4: 32741,
+        ac5: 32757,
+        ac6: 23153,
+        b1: 6190,
+        b2: 4,
+        mb: -32768,
+        mc: -8711,
+        md: 2868,
+    }
+}
+
+/// State of the simulated device, shared by [`MockI2C`] and [`MockDelay`].
+pub(crate) struct State {
+    /// Device ID.
+    pub id: u8,
+    /// Calibration data.
+    pub calibration: Calibration,
+    /// Raw temperature (`UT`).
+    pub raw_temperature: i16,
+    /// Raw pressure (`UP`) in ultra low power mode.
+    ///
+    /// Scaled with the requested oversampling setting, so every mode yields the same pressure.
+    pub raw_pressure: i32,
+    /// Toggle the least significant bit of the raw values after each conversion.
+    pub noise: bool,
+    /// Conversions never complete.
+    pub stuck_conversion: bool,
+    /// Number of upcoming transactions failing with a missing acknowledge.
+    pub failures: usize,
+    /// Address and written bytes of each transaction.
+    pub writes: Vec<(u8, Vec<u8>)>,
+    /// Register pointer and length of each read.
+    pub reads: Vec<(u8, usize)>,
+    /// Number of reads of the result while a conversion was running.
+    pub early_reads: usize,
+    /// Total delay in nanoseconds.
+    pub delayed_ns: u64,
+    /// Register pointer.
+    register: u8,
+    /// Last command written to the control register.
+    command: u8,
+    /// Whether a conversion is running.
+    converting: bool,
+    /// Remaining time of the running conversion in nanoseconds.
+    remaining_ns: u32,
+    /// Whether the `EOC` pin still reads high right after the conversion command.
+    eoc_stale: bool,
+}
+
+impl Default for State {
+    /// Datasheet example values, raw temperature `27898` and raw pressure `23843`.
+    fn default() -> Self {
+        Self {
+            id: 0x55,
+            calibration: calibration(),
+            raw_temperature: 27898,
+            raw_pressure: 23843,
+            noise: false,
+            stuck_conversion: false,
+            failures: 0,
+            writes: Vec::new(),
+            reads: Vec::new(),
+            early_reads: 0,
+            delayed_ns: 0,
+            register: 0,
+            command: 0,
+            converting: false,
+            remaining_ns: 0,
+            eoc_stale: false,
+        }
+    }
+}
+
+impl State {
+    /// Fill `read` with the content of the registers starting at the register pointer.
+    fn fill(&self, read: &mut [u8]) {
+        let mut data = [0u8; 22];
+
+        match self.register {
+            register if register == Register::ChipId as u8 => data[0] = self.id,
+            register
+                if (Register::CalibrationAc1 as u8..=Register::CalibrationMd as u8 + 1)
+                    .contains(&register) =>
+            {
+                let bytes = self.calibration.to_bytes();
+                let offset = (register - Register::CalibrationAc1 as u8) as usize;
+
+                data[..bytes.len() - offset].copy_from_slice(&bytes[offset..]);
+            }
+            register if register == Register::Control as u8 => {
+                // Every command starts a conversion by setting `SCO`, the device clears it when done.
+                data[0] = self.command & !SCO | if self.converting { SCO } else { 0 };
+            }
+            register if register == Register::TempPressureData as u8 => {
+                if self.command == Register::ReadTempCmd as u8 {
+                    data[..2].copy_from_slice(&self.raw_temperature.to_be_bytes());
+                } else {
+                    data[..4].copy_from_slice(&(self.raw_pressure << 16).to_be_bytes());
+                }
+            }
+            _ => {}
+        }
+
+        let len = read.len().min(data.len());
+
+        read[..len].copy_from_slice(&data[..len]);
+    }
+
+    /// Write `bytes` starting with the register address.
+    fn write(&mut self, bytes: &[u8]) {
+        let [register, rest @ ..] = bytes else {
+            return;
+        };
+
+        self.register = *register;
+
+        if *register != Register::Control as u8 {
+            return;
+        }
+
+        if let [command, ..] = rest {
+            self.command = *command;
+            self.converting = true;
+            self.remaining_ns = CONVERSION_NS;
+            self.eoc_stale = true;
+
+            if self.noise {
+                if *command == Register::ReadTempCmd as u8 {
+                    self.raw_temperature ^= 1;
+                } else {
+                    self.raw_pressure ^= 1;
+                }
+            }
+        }
+    }
+
+    /// Run a transaction.
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        let mut written = Vec::new();
+
+        for operation in operations.iter() {
+            if let Operation::Write(bytes) = operation {
+                written.extend_from_slice(bytes);
+            }
+        }
+
+        self.writes.push((address, written));
+
+        if self.failures > 0 {
+            self.failures -= 1;
+
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => self.write(bytes),
+                Operation::Read(buffer) => {
+                    if self.converting && self.register == Register::TempPressureData as u8 {
+                        self.early_reads += 1;
+                    }
+
+                    self.reads.push((self.register, buffer.len()));
+                    self.fill(buffer);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Simulated `BMP180` I2C bus.
+pub(crate) struct MockI2C(Rc<RefCell<State>>);
+
+/// Delay of the simulated `BMP180`, advances running conversions.
+#[derive(Clone)]
+pub(crate) struct MockDelay(Rc<RefCell<State>>);
+
+/// `EOC` pin of the simulated device.
+///
+/// Low while a conversion is running, but still reads high once right after the conversion command.
+pub(crate) struct MockEoc(pub Rc<RefCell<State>>);
+
+impl MockEoc {
+    /// Level of the pin, consuming the stale high level after a conversion command.
+    fn is_high(&self) -> bool {
+        let mut state = self.0.borrow_mut();
+
+        let stale = core::mem::take(&mut state.eoc_stale);
+
+        stale || !state.converting
+    }
+}
+
+impl embedded_hal::digital::ErrorType for MockEoc {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::digital::InputPin for MockEoc {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(MockEoc::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!MockEoc::is_high(self))
+    }
+}
+
+/// Resolves immediately, the conversion completes on the awaited edge.
+#[cfg(feature = "async")]
+impl embedded_hal_async::digital::Wait for MockEoc {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        if !MockEoc::is_high(self) {
+            self.0.borrow_mut().converting = false;
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().eoc_stale = false;
+
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        let mut state = self.0.borrow_mut();
+
+        state.eoc_stale = false;
+        state.converting = false;
+
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_low().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_rising_edge().await
+    }
+}
+
+/// Create a simulated `BMP180` device with the datasheet example values.
+///
+/// Returns the bus, the delay and the shared state.
+pub(crate) fn mock() -> (MockI2C, MockDelay, Rc<RefCell<State>>) {
+    let state = Rc::new(RefCell::new(State::default()));
+
+    (MockI2C(state.clone()), MockDelay(state.clone()), state)
+}
+
+impl embedded_hal::i2c::ErrorType for MockI2C {
+    type Error = ErrorKind;
+}
+
+#[duplicate_item(
+    feature_        module        async     i2c_trait                       delay_trait;
+    ["async"]       [asynch]      [async]   [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs];
+    ["blocking"]    [blocking]    []        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs];
+)]
+mod module {
+    #[cfg(feature=feature_)]
+    mod inner {
+        use embedded_hal::i2c::Operation;
+
+        use super::super::{MockDelay, MockI2C};
+
+        impl delay_trait for MockDelay {
+            async fn delay_ns(&mut self, ns: u32) {
+                let mut state = self.0.borrow_mut();
+
+                state.delayed_ns += ns as u64;
+                state.remaining_ns = state.remaining_ns.saturating_sub(ns);
+
+                if state.remaining_ns == 0 && !state.stuck_conversion {
+                    state.converting = false;
+                }
+            }
+        }
+
+        impl i2c_trait for MockI2C {
+            async fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [Operation<'_>],
+            ) -> Result<(), Self::Error> {
+                self.0.borrow_mut().transaction(address, operations)
+            }
+        }
+    }
+}
@@ -2,9 +2,10 @@
 
 /// Operating mode.
 #[repr(u8)]
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
 #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mode {
     /// Ultra low power mode.
     UltraLowPower = 0,
@@ -18,6 +19,14 @@ pub enum Mode {
 }
 
 impl Mode {
+    /// All modes.
+    pub const ALL: [Mode; 4] = [
+        Mode::UltraLowPower,
+        Mode::Standard,
+        Mode::HighResolution,
+        Mode::UltraHighResolution,
+    ];
+
     /// Delay in milliseconds for the given mode.
     pub fn delay_ms(&self) -> u32 {
         match self {
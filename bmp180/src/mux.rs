@@ -0,0 +1,468 @@
+//! `TCA9548A` I2C multiplexer support.
+//!
+//! The `BMP180` has a single fixed address, multiple devices can be put behind a `TCA9548A` multiplexer.
+//! Each [`MuxChannel`] implements the I2C traits and selects its channel before each transaction,
+//! so it can be passed to `UninitBMP180::builder` like any other I2C bus.
+
+use core::cell::Cell;
+
+#[cfg(not(feature = "async"))]
+use core::cell::RefCell;
+
+#[cfg(feature = "async")]
+use embassy_sync::{blocking_mutex::raw::NoopRawMutex, mutex::Mutex};
+
+use duplicate::duplicate_item;
+
+/// `TCA9548A` channel.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum Tca9548aChannel {
+    /// Channel 0.
+    Channel0 = 0,
+    /// Channel 1.
+    Channel1 = 1,
+    /// Channel 2.
+    Channel2 = 2,
+    /// Channel 3.
+    Channel3 = 3,
+    /// Channel 4.
+    Channel4 = 4,
+    /// Channel 5.
+    Channel5 = 5,
+    /// Channel 6.
+    Channel6 = 6,
+    /// Channel 7.
+    Channel7 = 7,
+}
+
+impl Tca9548aChannel {
+    /// All channels.
+    pub const ALL: [Tca9548aChannel; 8] = [
+        Tca9548aChannel::Channel0,
+        Tca9548aChannel::Channel1,
+        Tca9548aChannel::Channel2,
+        Tca9548aChannel::Channel3,
+        Tca9548aChannel::Channel4,
+        Tca9548aChannel::Channel5,
+        Tca9548aChannel::Channel6,
+        Tca9548aChannel::Channel7,
+    ];
+
+    /// Value of the control register selecting this channel.
+    fn mask(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+/// Shared I2C bus.
+///
+/// An async mutex if the `async` feature is enabled, so async channels wait for the bus without spinning.
+#[cfg(feature = "async")]
+type Bus<I2C> = Mutex<NoopRawMutex, I2C>;
+
+/// Shared I2C bus.
+#[cfg(not(feature = "async"))]
+type Bus<I2C> = RefCell<I2C>;
+
+/// `TCA9548A` I2C multiplexer.
+///
+/// Owns the I2C bus and hands out [`MuxChannel`]s sharing it.
+/// The multiplexer must not be shared across threads or executors.
+/// Async channels wait for the bus to be released by other tasks on the same executor.
+/// Blocking channels must not be used re-entrantly or while an async transaction is pending.
+pub struct Tca9548a<I2C> {
+    /// Multiplexer I2C address.
+    addr: u8,
+    /// Shared I2C bus.
+    i2c: Bus<I2C>,
+    /// Currently selected channel, `None` if unknown.
+    selected: Cell<Option<Tca9548aChannel>>,
+}
+
+impl<I2C> Tca9548a<I2C> {
+    /// Default I2C address.
+    pub const DEFAULT_ADDR: u8 = 0x70;
+
+    /// Create a new multiplexer with the given I2C address (`0x70` to `0x77`).
+    pub fn new(addr: u8, i2c: I2C) -> Self {
+        Self {
+            addr,
+            i2c: Bus::new(i2c),
+            selected: Cell::new(None),
+        }
+    }
+
+    /// I2C bus device for the given channel.
+    pub fn channel(&self, channel: Tca9548aChannel) -> MuxChannel<'_, I2C> {
+        MuxChannel { mux: self, channel }
+    }
+
+    /// Release the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c.into_inner()
+    }
+
+    /// Borrow the I2C bus for a blocking transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bus is in use.
+    #[cfg(feature = "blocking")]
+    fn lock_blocking(&self) -> impl core::ops::DerefMut<Target = I2C> + '_ {
+        #[cfg(feature = "async")]
+        let i2c = self.i2c.try_lock().expect("I2C bus is in use");
+
+        #[cfg(not(feature = "async"))]
+        let i2c = self.i2c.borrow_mut();
+
+        i2c
+    }
+}
+
+/// I2C bus device behind a [`Tca9548a`] channel.
+pub struct MuxChannel<'mux, I2C> {
+    /// Multiplexer.
+    mux: &'mux Tca9548a<I2C>,
+    /// Channel to select before each transaction.
+    channel: Tca9548aChannel,
+}
+
+impl<I2C> MuxChannel<'_, I2C> {
+    /// Multiplexer channel.
+    pub fn channel(&self) -> Tca9548aChannel {
+        self.channel
+    }
+}
+
+impl<I2C> embedded_hal::i2c::ErrorType for MuxChannel<'_, I2C>
+where
+    I2C: embedded_hal::i2c::ErrorType,
+{
+    type Error = I2C::Error;
+}
+
+#[duplicate_item(
+    feature_        module        async     await               i2c_trait                       delay_trait                             lock_bus;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs]    [i2c.lock().await];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs]          [lock_blocking()];
+)]
+pub mod module {
+    //! Multiple `BMP180` devices behind a `TCA9548A` multiplexer.
+
+    #[cfg(feature=feature_)]
+    mod inner {
+        use crate::{
+            device::{module::UninitBMP180, module::BMP180, Identity},
+            error::BMP180Error,
+            mode::Mode,
+            tri,
+        };
+
+        use embedded_hal::i2c::Operation;
+
+        use super::super::{MuxChannel, Tca9548a, Tca9548aChannel};
+
+        impl<I2C> i2c_trait for MuxChannel<'_, I2C>
+        where
+            I2C: i2c_trait,
+        {
+            async fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [Operation<'_>],
+            ) -> Result<(), Self::Error> {
+                let mut i2c = self.mux.lock_bus;
+
+                if self.mux.selected.get() != Some(self.channel) {
+                    self.mux.selected.set(None);
+
+                    tri!(i2c.write(self.mux.addr, &[self.channel.mask()]).await);
+
+                    self.mux.selected.set(Some(self.channel));
+                }
+
+                i2c.transaction(address, operations).await
+            }
+        }
+
+        /// Collection of `BMP180` devices behind a [`Tca9548a`] multiplexer, at most one per channel.
+        ///
+        /// Each device keeps its own calibration data.
+        pub struct MuxedBMP180<'mux, I2C, DELAY> {
+            /// Multiplexer.
+            mux: &'mux Tca9548a<I2C>,
+            /// Delay provider, cloned for each device.
+            delay: DELAY,
+            /// Devices indexed by channel.
+            devices: [Option<BMP180<MuxChannel<'mux, I2C>, DELAY>>; 8],
+        }
+
+        impl<'mux, I2C, DELAY> MuxedBMP180<'mux, I2C, DELAY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait + Clone,
+        {
+            /// Create an empty collection.
+            pub fn new(mux: &'mux Tca9548a<I2C>, delay: DELAY) -> Self {
+                Self {
+                    mux,
+                    delay,
+                    devices: core::array::from_fn(|_| None),
+                }
+            }
+
+            /// Initialize the device on the given channel and add it to the collection.
+            ///
+            /// Replaces a device previously added on the same channel.
+            pub async fn add(
+                &mut self,
+                channel: Tca9548aChannel,
+                mode: Mode,
+            ) -> Result<(), BMP180Error<I2C::Error>> {
+                let bmp180 = tri!(
+                    UninitBMP180::builder(self.mux.channel(channel), self.delay.clone())
+                        .mode(mode)
+                        .build()
+                        .initialize()
+                        .await
+                );
+
+                self.devices[channel as usize] = Some(bmp180);
+
+                Ok(())
+            }
+
+            /// Remove the device on the given channel from the collection.
+            pub fn remove(
+                &mut self,
+                channel: Tca9548aChannel,
+            ) -> Option<BMP180<MuxChannel<'mux, I2C>, DELAY>> {
+                self.devices[channel as usize].take()
+            }
+
+            /// Device on the given channel.
+            pub fn get(
+                &self,
+                channel: Tca9548aChannel,
+            ) -> Option<&BMP180<MuxChannel<'mux, I2C>, DELAY>> {
+                self.devices[channel as usize].as_ref()
+            }
+
+            /// Mutable device on the given channel.
+            pub fn get_mut(
+                &mut self,
+                channel: Tca9548aChannel,
+            ) -> Option<&mut BMP180<MuxChannel<'mux, I2C>, DELAY>> {
+                self.devices[channel as usize].as_mut()
+            }
+
+            /// Devices in the collection with their channels.
+            pub fn iter(
+                &self,
+            ) -> impl Iterator<Item = (Tca9548aChannel, &BMP180<MuxChannel<'mux, I2C>, DELAY>)>
+            {
+                Tca9548aChannel::ALL
+                    .into_iter()
+                    .zip(self.devices.iter())
+                    .filter_map(|(channel, device)| device.as_ref().map(|device| (channel, device)))
+            }
+
+            /// Update temperature and pressure of all devices.
+            ///
+            /// Returns the result of each update indexed by channel, `None` for empty channels.
+            pub async fn update_all(&mut self) -> [Option<Result<(), BMP180Error<I2C::Error>>>; 8] {
+                let mut results: [Option<Result<(), BMP180Error<I2C::Error>>>; 8] =
+                    core::array::from_fn(|_| None);
+
+                for (result, device) in results.iter_mut().zip(self.devices.iter_mut()) {
+                    if let Some(device) = device {
+                        *result = Some(device.update().await);
+                    }
+                }
+
+                results
+            }
+        }
+    }
+
+    #[cfg(feature=feature_)]
+    pub use inner::*;
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use std::{vec, vec::Vec};
+
+    use embassy_futures::{block_on, join::join, yield_now};
+    use embedded_hal_async::i2c::I2c;
+
+    use crate::{
+        asynch::MuxedBMP180,
+        mock::{mock, MockI2C},
+        Mode,
+    };
+
+    use super::*;
+
+    #[test]
+    fn selects_channel_once() {
+        let (i2c, _, state) = mock();
+
+        let mux = Tca9548a::new(Tca9548a::<()>::DEFAULT_ADDR, i2c);
+        let mut channel0 = mux.channel(Tca9548aChannel::Channel0);
+        let mut channel3 = mux.channel(Tca9548aChannel::Channel3);
+
+        block_on(async {
+            channel0.write(0x77, &[0xD0]).await.unwrap();
+            channel0.write(0x77, &[0xF4]).await.unwrap();
+            channel3.write(0x77, &[0xD0]).await.unwrap();
+            channel0.write(0x77, &[0xF6]).await.unwrap();
+        });
+
+        assert_eq!(
+            state.borrow().writes,
+            [
+                (0x70, vec![0b0000_0001]),
+                (0x77, vec![0xD0]),
+                (0x77, vec![0xF4]),
+                (0x70, vec![0b0000_1000]),
+                (0x77, vec![0xD0]),
+                (0x70, vec![0b0000_0001]),
+                (0x77, vec![0xF6]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reselects_channel_after_failed_selection() {
+        let (i2c, _, state) = mock();
+
+        let mux = Tca9548a::new(0x74, i2c);
+        let mut channel = mux.channel(Tca9548aChannel::Channel7);
+
+        state.borrow_mut().failures = 1;
+
+        block_on(async {
+            assert!(channel.write(0x77, &[0xD0]).await.is_err());
+            channel.write(0x77, &[0xD0]).await.unwrap();
+        });
+
+        assert_eq!(
+            state.borrow().writes,
+            [
+                (0x74, vec![0b1000_0000]),
+                (0x74, vec![0b1000_0000]),
+                (0x77, vec![0xD0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn muxed_devices() {
+        let (i2c, delay, state) = mock();
+
+        let mux = Tca9548a::new(Tca9548a::<()>::DEFAULT_ADDR, i2c);
+        let mut devices = MuxedBMP180::new(&mux, delay);
+
+        block_on(async {
+            devices
+                .add(Tca9548aChannel::Channel1, Mode::UltraLowPower)
+                .await
+                .unwrap();
+            devices
+                .add(Tca9548aChannel::Channel5, Mode::UltraLowPower)
+                .await
+                .unwrap();
+
+            state.borrow_mut().writes.clear();
+
+            let results = devices.update_all().await;
+
+            for channel in Tca9548aChannel::ALL {
+                let added = matches!(
+                    channel,
+                    Tca9548aChannel::Channel1 | Tca9548aChannel::Channel5
+                );
+
+                assert_eq!(results[channel as usize].is_some(), added);
+            }
+        });
+
+        let selections: Vec<_> = state
+            .borrow()
+            .writes
+            .iter()
+            .filter(|(address, _)| *address == 0x70)
+            .map(|(_, bytes)| bytes.clone())
+            .collect();
+
+        assert_eq!(selections, [vec![0b0000_0010], vec![0b0010_0000]]);
+
+        for (_, device) in devices.iter() {
+            assert_eq!(device.pressure(), 69964);
+        }
+    }
+
+    /// Bus yielding to the executor before each transaction.
+    struct YieldingI2C(MockI2C);
+
+    impl embedded_hal::i2c::ErrorType for YieldingI2C {
+        type Error = embedded_hal::i2c::ErrorKind;
+    }
+
+    impl I2c for YieldingI2C {
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [embedded_hal::i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            yield_now().await;
+
+            self.0.transaction(address, operations).await
+        }
+    }
+
+    #[test]
+    fn concurrent_channels_wait_for_bus() {
+        let (i2c, _, state) = mock();
+
+        let mux = Tca9548a::new(Tca9548a::<()>::DEFAULT_ADDR, YieldingI2C(i2c));
+        let mut channel0 = mux.channel(Tca9548aChannel::Channel0);
+        let mut channel3 = mux.channel(Tca9548aChannel::Channel3);
+
+        let (first, second) = block_on(join(
+            channel0.write(0x77, &[0xD0]),
+            channel3.write(0x77, &[0xF4]),
+        ));
+
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(
+            state.borrow().writes,
+            [
+                (0x70, vec![0b0000_0001]),
+                (0x77, vec![0xD0]),
+                (0x70, vec![0b0000_1000]),
+                (0x77, vec![0xF4]),
+            ]
+        );
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_selects_channel() {
+        let (i2c, _, state) = mock();
+
+        let mux = Tca9548a::new(Tca9548a::<()>::DEFAULT_ADDR, i2c);
+        let mut channel = mux.channel(Tca9548aChannel::Channel2);
+
+        embedded_hal::i2c::I2c::write(&mut channel, 0x77, &[0xD0]).unwrap();
+
+        assert_eq!(
+            state.borrow().writes,
+            [(0x70, vec![0b0000_0100]), (0x77, vec![0xD0])]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Optional GPIO pins.
+
+use core::convert::Infallible;
+
+#[cfg(feature = "async")]
+use core::{future::Future, task::Poll};
+
+/// Placeholder for an absent pin.
+///
+/// Used as the default type of the optional `EOC` and `XCLR` pins. Can not be constructed.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum NoPin {}
+
+impl embedded_hal::digital::ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl embedded_hal::digital::InputPin for NoPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        match *self {}
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        match *self {}
+    }
+}
+
+impl embedded_hal::digital::OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::digital::Wait for NoPin {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        match *self {}
+    }
+}
+
+/// Interval in microseconds between two polls of the `EOC` pin.
+#[cfg(feature = "blocking")]
+const EOC_POLL_INTERVAL_US: u32 = 100;
+
+/// Wait for the `EOC` pin to go low and then high again by polling it.
+///
+/// `EOC` is high while the device is idle and may still be high right after the conversion command,
+/// so a high level only marks a new result once the pin was seen low.
+///
+/// Gives up after `timeout_ms`, the maximum conversion time, and assumes the conversion is complete.
+#[cfg(feature = "blocking")]
+pub(crate) fn wait_for_eoc<P, D>(
+    pin: &mut P,
+    delay: &mut D,
+    timeout_ms: u32,
+) -> Result<(), P::Error>
+where
+    P: embedded_hal::digital::InputPin,
+    D: embedded_hal::delay::DelayNs,
+{
+    let mut elapsed_us = 0;
+    let mut started = false;
+
+    while elapsed_us < timeout_ms * 1000 {
+        let high = crate::tri!(pin.is_high());
+
+        if high && started {
+            return Ok(());
+        }
+
+        started |= !high;
+
+        delay.delay_us(EOC_POLL_INTERVAL_US);
+
+        elapsed_us += EOC_POLL_INTERVAL_US;
+    }
+
+    Ok(())
+}
+
+/// Wait for a rising edge of the `EOC` pin.
+///
+/// `EOC` is high while the device is idle and may still be high right after the conversion command,
+/// so only the rising edge at the end of the conversion marks a new result.
+///
+/// Gives up after `timeout_ms`, the maximum conversion time, and assumes the conversion is complete.
+#[cfg(feature = "async")]
+pub(crate) async fn wait_for_eoc_async<P, D>(
+    pin: &mut P,
+    delay: &mut D,
+    timeout_ms: u32,
+) -> Result<(), P::Error>
+where
+    P: embedded_hal_async::digital::Wait,
+    D: embedded_hal_async::delay::DelayNs,
+{
+    let mut eoc = core::pin::pin!(pin.wait_for_rising_edge());
+    let mut timeout = core::pin::pin!(delay.delay_ms(timeout_ms));
+
+    core::future::poll_fn(|cx| {
+        if let Poll::Ready(result) = eoc.as_mut().poll(cx) {
+            return Poll::Ready(result);
+        }
+
+        if timeout.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Ok(()));
+        }
+
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(all(test, any(feature = "async", feature = "blocking")))]
+mod tests {
+    use crate::{
+        mock::{mock, MockEoc},
+        Mode,
+    };
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_update_waits_for_rising_edge() {
+        use embassy_futures::block_on;
+
+        use crate::asynch::UninitBMP180;
+
+        let (i2c, delay, state) = mock();
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(i2c, delay)
+                .mode(Mode::UltraLowPower)
+                .eoc(MockEoc(state.clone()))
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        assert_eq!(state.borrow().early_reads, 0);
+        assert_eq!(bmp180.temperature(), 150);
+        assert_eq!(bmp180.pressure(), 69964);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_update_waits_for_low_then_high() {
+        use crate::blocking::UninitBMP180;
+
+        let (i2c, delay, state) = mock();
+
+        let mut bmp180 = UninitBMP180::builder(i2c, delay)
+            .mode(Mode::UltraLowPower)
+            .eoc(MockEoc(state.clone()))
+            .build()
+            .initialize()
+            .unwrap();
+
+        state.borrow_mut().delayed_ns = 0;
+
+        bmp180.update().unwrap();
+
+        // Both conversions end on `EOC` before the maximum conversion time of `5 ms`.
+        assert!(state.borrow().delayed_ns < 2 * 5_000_000);
+        assert_eq!(state.borrow().early_reads, 0);
+        assert_eq!(bmp180.temperature(), 150);
+        assert_eq!(bmp180.pressure(), 69964);
+    }
+}
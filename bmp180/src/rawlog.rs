@@ -0,0 +1,647 @@
+//! Offline decoder for raw sample logs.
+//!
+//! Logging the raw readings (`UT` and `UP`) instead of the compensated values saves time on the MCU.
+//! The [`Decoder`] applies the exact compensation used by the devices on the host and outputs temperature,
+//! pressure and altitude.
+//!
+//! # CSV format
+//!
+//! ```text
+//! calibration,408,-72,-14383,32741,32757,23153,6190,4,-32768,-8711,2868
+//! ut,up,mode
+//! 27898,23843,0
+//! ```
+//!
+//! A `calibration` line contains `AC1` to `MD` in datasheet order and applies to all following samples.
+//! Each sample line contains the raw temperature, the raw pressure and the mode (`0` to `3`).
+//! The `ut,up,mode` header, empty lines and lines starting with `#` are skipped.
+//!
+//! # Binary format
+//!
+//! The binary format is the record format of the [`record`](crate::record) module,
+//! a [`Header`] carrying the calibration data followed by [`Record`]s.
+//! The stored true temperature and pressure are ignored, the compensation is applied to the raw values again.
+//!
+//! Only available when the `std` feature is enabled.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Read, Write},
+    vec::Vec,
+};
+
+use crate::{
+    calibration::Calibration,
+    compensation::{compute_altitude, compute_pressure, compute_temperature},
+    error::ArithmeticError,
+    mode::Mode,
+    record::{Header, Record, RecordError, VERSION},
+    tri,
+};
+
+pub use crate::record::MAGIC;
+
+/// Raw sample.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    /// Mode used to measure the raw pressure.
+    pub mode: Mode,
+    /// Raw temperature (`UT`).
+    pub raw_temperature: i16,
+    /// Raw pressure (`UP`).
+    pub raw_pressure: i32,
+}
+
+/// Decoded sample.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Measurement {
+    /// Raw sample.
+    pub sample: Sample,
+    /// True temperature in `0.1 C`.
+    pub temperature: i32,
+    /// True pressure in `Pa`.
+    pub pressure: i32,
+    /// Altitude in meters.
+    pub altitude: f32,
+}
+
+/// Error decoding a raw sample log.
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum DecodeError {
+    /// I/O error.
+    Io(io::Error),
+    /// The binary log does not start with the [`MAGIC`] bytes.
+    InvalidMagic,
+    /// The binary log has an unsupported version.
+    UnsupportedVersion(u8),
+    /// The binary log ends within the header or a record.
+    Truncated,
+    /// Invalid header or record in a binary log.
+    Record {
+        /// Index of the record, `None` for the header.
+        index: Option<usize>,
+        /// Record error.
+        error: RecordError,
+    },
+    /// Invalid line in a CSV log, starting at `1`.
+    InvalidLine(usize),
+    /// Samples without calibration data.
+    MissingCalibration,
+    /// Arithmetic error computing the sample with the given index, starting at `0`.
+    Arithmetic {
+        /// Index of the sample.
+        index: usize,
+        /// Arithmetic error.
+        error: ArithmeticError,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(error) => write!(f, "I/O error: {error}"),
+            DecodeError::InvalidMagic => f.write_str("invalid magic bytes"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported version: {version}")
+            }
+            DecodeError::Truncated => f.write_str("truncated sample"),
+            DecodeError::Record { index: None, error } => write!(f, "invalid header: {error}"),
+            DecodeError::Record {
+                index: Some(index),
+                error,
+            } => write!(f, "invalid record {index}: {error}"),
+            DecodeError::InvalidLine(line) => write!(f, "invalid line: {line}"),
+            DecodeError::MissingCalibration => f.write_str("missing calibration data"),
+            DecodeError::Arithmetic { index, error } => {
+                write!(f, "arithmetic error in sample {index}: {error}")
+            }
+        }
+    }
+}
+
+/// Only available when the `impl-debug` feature is enabled.
+#[cfg(feature = "impl-debug")]
+impl std::error::Error for DecodeError {}
+
+impl From<io::Error> for DecodeError {
+    fn from(error: io::Error) -> Self {
+        DecodeError::Io(error)
+    }
+}
+
+/// Raw sample log decoder.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct Decoder {
+    /// Calibration data for logs without calibration data.
+    calibration: Option<Calibration>,
+    /// Sea level pressure in `Pa` used to compute the altitude.
+    sea_level_pressure: f32,
+}
+
+impl Decoder {
+    /// Create a new decoder without calibration data, using the standard sea level pressure of `101325 Pa`.
+    pub fn new() -> Self {
+        Self {
+            calibration: None,
+            sea_level_pressure: 101325.0,
+        }
+    }
+
+    /// Set the calibration data for CSV logs without a `calibration` line.
+    ///
+    /// Overridden by the calibration lines of CSV logs, binary logs always use the calibration data of their header.
+    pub fn calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = Some(calibration);
+        self
+    }
+
+    /// Set the sea level pressure in `Pa` used to compute the altitude.
+    pub fn sea_level_pressure(mut self, sea_level_pressure: f32) -> Self {
+        self.sea_level_pressure = sea_level_pressure;
+        self
+    }
+
+    /// Decode a single sample.
+    pub fn decode(
+        &self,
+        calibration: &Calibration,
+        sample: Sample,
+    ) -> Result<Measurement, ArithmeticError> {
+        let temperature = tri!(compute_temperature(calibration, sample.raw_temperature));
+        let pressure = tri!(compute_pressure(
+            calibration,
+            sample.mode,
+            sample.raw_temperature,
+            sample.raw_pressure
+        ));
+
+        Ok(Measurement {
+            sample,
+            temperature,
+            pressure,
+            altitude: compute_altitude(pressure, self.sea_level_pressure),
+        })
+    }
+
+    /// Decode a CSV log.
+    pub fn decode_csv<R: BufRead>(&self, reader: R) -> Result<Vec<Measurement>, DecodeError> {
+        let mut calibration = self.calibration.clone();
+        let mut measurements = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = tri!(line.map_err(DecodeError::Io));
+            let line = line.trim();
+            let invalid = || DecodeError::InvalidLine(index + 1);
+
+            if line.is_empty() || line.starts_with('#') || line == "ut,up,mode" {
+                continue;
+            }
+
+            if let Some(values) = line.strip_prefix("calibration,") {
+                calibration = Some(tri!(parse_calibration(values).ok_or_else(invalid)));
+
+                continue;
+            }
+
+            let sample = tri!(parse_sample(line).ok_or_else(invalid));
+            let calibration = tri!(calibration.as_ref().ok_or(DecodeError::MissingCalibration));
+
+            measurements.push(tri!(self.decode_sample(
+                calibration,
+                sample,
+                measurements.len()
+            )));
+        }
+
+        Ok(measurements)
+    }
+
+    /// Decode a binary log.
+    pub fn decode_binary<R: Read>(&self, mut reader: R) -> Result<Vec<Measurement>, DecodeError> {
+        let mut header = [0u8; Header::LEN];
+        let version = MAGIC.len();
+
+        tri!(reader
+            .read_exact(&mut header[..=version])
+            .map_err(truncated(DecodeError::InvalidMagic)));
+
+        if header[..version] != MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+
+        if header[version] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(header[version]));
+        }
+
+        tri!(reader
+            .read_exact(&mut header[version + 1..])
+            .map_err(truncated(DecodeError::Truncated)));
+
+        let header =
+            tri!(Header::from_bytes(&header)
+                .map_err(|error| DecodeError::Record { index: None, error }));
+
+        self.decode_records(&header, reader)
+    }
+
+    /// Decode the records following the header of a binary log.
+    fn decode_records<R: Read>(
+        &self,
+        header: &Header,
+        mut reader: R,
+    ) -> Result<Vec<Measurement>, DecodeError> {
+        let mut bytes = Vec::new();
+
+        tri!(reader.read_to_end(&mut bytes).map_err(DecodeError::Io));
+
+        let chunks = bytes.chunks_exact(Record::LEN);
+
+        if !chunks.remainder().is_empty() {
+            return Err(DecodeError::Truncated);
+        }
+
+        let mut measurements = Vec::with_capacity(chunks.len());
+
+        for chunk in chunks {
+            let index = measurements.len();
+            let mut record = [0u8; Record::LEN];
+
+            record.copy_from_slice(chunk);
+
+            let record = tri!(
+                Record::from_bytes(&record).map_err(|error| DecodeError::Record {
+                    index: Some(index),
+                    error,
+                })
+            );
+
+            let sample = Sample {
+                mode: record.mode,
+                raw_temperature: record.raw_temperature,
+                raw_pressure: record.raw_pressure,
+            };
+
+            measurements.push(tri!(self.decode_sample(&header.calibration, sample, index)));
+        }
+
+        Ok(measurements)
+    }
+
+    /// Decode the sample with the given index.
+    fn decode_sample(
+        &self,
+        calibration: &Calibration,
+        sample: Sample,
+        index: usize,
+    ) -> Result<Measurement, DecodeError> {
+        self.decode(calibration, sample)
+            .map_err(|error| DecodeError::Arithmetic { index, error })
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map an unexpected end of file to `error`.
+fn truncated(error: DecodeError) -> impl FnOnce(io::Error) -> DecodeError {
+    move |io_error| match io_error.kind() {
+        io::ErrorKind::UnexpectedEof => error,
+        _ => DecodeError::Io(io_error),
+    }
+}
+
+/// Parse the calibration values of a CSV calibration line.
+fn parse_calibration(values: &str) -> Option<Calibration> {
+    let mut values = values.split(',').map(str::trim);
+
+    let mut next = || values.next()?.parse::<i32>().ok();
+
+    let calibration = Calibration {
+        ac1: i16::try_from(next()?).ok()?,
+        ac2: i16::try_from(next()?).ok()?,
+        ac3: i16::try_from(next()?).ok()?,
+        ac4: u16::try_from(next()?).ok()?,
+        ac5: u16::try_from(next()?).ok()?,
+        ac6: u16::try_from(next()?).ok()?,
+        b1: i16::try_from(next()?).ok()?,
+        b2: i16::try_from(next()?).ok()?,
+        mb: i16::try_from(next()?).ok()?,
+        mc: i16::try_from(next()?).ok()?,
+        md: i16::try_from(next()?).ok()?,
+    };
+
+    if values.next().is_some() {
+        return None;
+    }
+
+    Some(calibration)
+}
+
+/// Parse a CSV sample line.
+fn parse_sample(line: &str) -> Option<Sample> {
+    let mut values = line.split(',').map(str::trim);
+
+    let raw_temperature = values.next()?.parse().ok()?;
+    let raw_pressure = values.next()?.parse().ok()?;
+    let mode = *Mode::ALL.get(values.next()?.parse::<usize>().ok()?)?;
+
+    if values.next().is_some() {
+        return None;
+    }
+
+    Some(Sample {
+        mode,
+        raw_temperature,
+        raw_pressure,
+    })
+}
+
+/// Write a CSV log.
+pub fn write_csv<W: Write>(
+    mut writer: W,
+    calibration: &Calibration,
+    samples: &[Sample],
+) -> io::Result<()> {
+    tri!(writeln!(
+        writer,
+        "calibration,{},{},{},{},{},{},{},{},{},{},{}",
+        calibration.ac1,
+        calibration.ac2,
+        calibration.ac3,
+        calibration.ac4,
+        calibration.ac5,
+        calibration.ac6,
+        calibration.b1,
+        calibration.b2,
+        calibration.mb,
+        calibration.mc,
+        calibration.md,
+    ));
+
+    tri!(writeln!(writer, "ut,up,mode"));
+
+    for sample in samples {
+        tri!(writeln!(
+            writer,
+            "{},{},{}",
+            sample.raw_temperature, sample.raw_pressure, sample.mode as u8
+        ));
+    }
+
+    Ok(())
+}
+
+/// Write a binary log.
+pub fn write_binary<W: Write>(
+    mut writer: W,
+    header: &Header,
+    records: &[Record],
+) -> io::Result<()> {
+    tri!(writer.write_all(&header.to_bytes()));
+
+    for record in records {
+        tri!(writer.write_all(&record.to_bytes()));
+    }
+
+    Ok(())
+}
+
+/// Write decoded samples as CSV, with the temperature in `C`, the pressure in `Pa` and the altitude in meters.
+pub fn write_measurements<W: Write>(mut writer: W, measurements: &[Measurement]) -> io::Result<()> {
+    tri!(writeln!(writer, "temperature,pressure,altitude"));
+
+    for measurement in measurements {
+        tri!(writeln!(
+            writer,
+            "{:.1},{},{:.2}",
+            measurement.temperature as f32 / 10.0,
+            measurement.pressure,
+            measurement.altitude
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec;
+
+    use crate::record::Flags;
+
+    use super::*;
+
+    /// Calibration data of the datasheet example.
+    fn calibration() -> Calibration {
+        Calibration {
+            ac1: 408,
+            ac2: -72,
+            ac3: -14383,
+            ac4: 32741,
+            ac5: 32757,
+            ac6: 23153,
+            b1: 6190,
+            b2: 4,
+            mb: -32768,
+            mc: -8711,
+            md: 2868,
+        }
+    }
+
+    fn samples() -> Vec<Sample> {
+        vec![
+            Sample {
+                mode: Mode::UltraLowPower,
+                raw_temperature: 27898,
+                raw_pressure: 23843,
+            },
+            Sample {
+                mode: Mode::Standard,
+                raw_temperature: 27950,
+                raw_pressure: 47700,
+            },
+            Sample {
+                mode: Mode::HighResolution,
+                raw_temperature: 28001,
+                raw_pressure: 95400,
+            },
+            Sample {
+                mode: Mode::UltraHighResolution,
+                raw_temperature: 27800,
+                raw_pressure: 190_800,
+            },
+        ]
+    }
+
+    fn assert_decoded(measurements: &[Measurement]) {
+        let decoder = Decoder::new();
+        let calibration = calibration();
+
+        assert_eq!(measurements.len(), samples().len());
+
+        for (measurement, sample) in measurements.iter().zip(samples()) {
+            assert!(measurement.sample == sample);
+            assert!(*measurement == decoder.decode(&calibration, sample).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_datasheet_example() {
+        let measurement = Decoder::new().decode(&calibration(), samples()[0]).unwrap();
+
+        assert_eq!(measurement.temperature, 150);
+        assert_eq!(measurement.pressure, 69964);
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let mut log = Vec::new();
+
+        write_csv(&mut log, &calibration(), &samples()).unwrap();
+
+        let measurements = Decoder::new().decode_csv(log.as_slice()).unwrap();
+
+        assert_decoded(&measurements);
+    }
+
+    #[test]
+    fn csv_without_calibration() {
+        let result = Decoder::new().decode_csv("27898,23843,0\n".as_bytes());
+
+        assert!(matches!(result, Err(DecodeError::MissingCalibration)));
+    }
+
+    #[test]
+    fn csv_invalid_line() {
+        let mut log = Vec::new();
+
+        write_csv(&mut log, &calibration(), &samples()).unwrap();
+        log.extend_from_slice(b"27898,23843,4\n");
+
+        let result = Decoder::new().decode_csv(log.as_slice());
+
+        assert!(matches!(result, Err(DecodeError::InvalidLine(7))));
+    }
+
+    fn records() -> Vec<Record> {
+        let decoder = Decoder::new();
+        let calibration = calibration();
+
+        samples()
+            .into_iter()
+            .enumerate()
+            .map(|(timestamp, sample)| {
+                let measurement = decoder.decode(&calibration, sample).unwrap();
+
+                Record {
+                    timestamp: timestamp as u32,
+                    mode: sample.mode,
+                    flags: Flags::NONE,
+                    raw_temperature: sample.raw_temperature,
+                    raw_pressure: sample.raw_pressure,
+                    temperature: measurement.temperature as i16,
+                    pressure: measurement.pressure,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn record_round_trip() {
+        for record in records() {
+            assert_eq!(Record::from_bytes(&record.to_bytes()), Ok(record));
+        }
+
+        let header = Header::new(calibration());
+
+        assert_eq!(Header::from_bytes(&header.to_bytes()), Ok(header));
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let mut log = Vec::new();
+
+        write_binary(&mut log, &Header::new(calibration()), &records()).unwrap();
+
+        assert_eq!(log.len(), Header::LEN + records().len() * Record::LEN);
+
+        let measurements = Decoder::new().decode_binary(log.as_slice()).unwrap();
+
+        assert_decoded(&measurements);
+
+        for (measurement, record) in measurements.iter().zip(records()) {
+            assert_eq!(measurement.temperature, record.temperature as i32);
+            assert_eq!(measurement.pressure, record.pressure);
+        }
+    }
+
+    #[test]
+    fn binary_truncated() {
+        let mut log = Vec::new();
+
+        write_binary(&mut log, &Header::new(calibration()), &records()).unwrap();
+        log.pop();
+
+        let result = Decoder::new().decode_binary(log.as_slice());
+
+        assert!(matches!(result, Err(DecodeError::Truncated)));
+
+        let result = Decoder::new().decode_binary(&log[..Header::LEN - 1]);
+
+        assert!(matches!(result, Err(DecodeError::Truncated)));
+    }
+
+    #[test]
+    fn records_corrupted() {
+        let mut log = Vec::new();
+
+        write_binary(&mut log, &Header::new(calibration()), &records()).unwrap();
+        log[Header::LEN + Record::LEN + 8] ^= 0x01;
+
+        let result = Decoder::new().decode_binary(log.as_slice());
+
+        assert!(matches!(
+            result,
+            Err(DecodeError::Record {
+                index: Some(1),
+                error: RecordError::Crc
+            })
+        ));
+
+        log[Header::LEN - 1] ^= 0x01;
+
+        let result = Decoder::new().decode_binary(log.as_slice());
+
+        assert!(matches!(
+            result,
+            Err(DecodeError::Record {
+                index: None,
+                error: RecordError::Crc
+            })
+        ));
+    }
+
+    #[test]
+    fn binary_invalid_header() {
+        let decoder = Decoder::new().calibration(calibration());
+
+        assert!(matches!(
+            decoder.decode_binary(b"BMPX\x01".as_slice()),
+            Err(DecodeError::InvalidMagic)
+        ));
+        assert!(matches!(
+            decoder.decode_binary(b"BMPL\x01".as_slice()),
+            Err(DecodeError::UnsupportedVersion(1))
+        ));
+    }
+}
@@ -0,0 +1,348 @@
+//! Binary record format for data loggers.
+//!
+//! A log starts with a [`Header`] carrying the calibration data, followed by fixed-size [`Record`]s,
+//! so loggers and analysis tools share one format.
+//! All values are big-endian, records and calibration data are protected by a `CRC-16/CCITT-FALSE`.
+//!
+//! | Offset | Length | Header                                            |
+//! |--------|--------|---------------------------------------------------|
+//! | `0`    | `4`    | [`MAGIC`]                                         |
+//! | `4`    | `1`    | [`VERSION`]                                       |
+//! | `5`    | `24`   | Calibration record, see `Calibration::to_record`  |
+//!
+//! | Offset | Length | Record                           |
+//! |--------|--------|----------------------------------|
+//! | `0`    | `4`    | Timestamp                        |
+//! | `4`    | `1`    | Mode                             |
+//! | `5`    | `1`    | [`Flags`]                        |
+//! | `6`    | `2`    | Raw temperature (`UT`)           |
+//! | `8`    | `4`    | Raw pressure (`UP`)              |
+//! | `12`   | `2`    | True temperature in `0.1 C`      |
+//! | `14`   | `4`    | True pressure in `Pa`            |
+//! | `18`   | `2`    | CRC of the preceding bytes       |
+
+use core::{fmt, ops::BitOr};
+
+use crate::{
+    calibration::Calibration,
+    crc::crc16,
+    health::{HealthIssue, HealthStatus},
+    mode::Mode,
+};
+
+/// Magic bytes at the start of a log.
+pub const MAGIC: [u8; 4] = *b"BMPL";
+
+/// Version of the format.
+pub const VERSION: u8 = 2;
+
+/// Error decoding a header or a record.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum RecordError {
+    /// The header does not start with the [`MAGIC`] bytes.
+    InvalidMagic,
+    /// The header has an unsupported version.
+    UnsupportedVersion(u8),
+    /// The CRC does not match.
+    Crc,
+    /// Invalid mode.
+    InvalidMode(u8),
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::InvalidMagic => f.write_str("invalid magic bytes"),
+            RecordError::UnsupportedVersion(version) => {
+                write!(f, "unsupported version: {version}")
+            }
+            RecordError::Crc => f.write_str("CRC mismatch"),
+            RecordError::InvalidMode(mode) => write!(f, "invalid mode: {mode}"),
+        }
+    }
+}
+
+/// Only available when the `impl-debug` feature is enabled.
+#[cfg(feature = "impl-debug")]
+impl core::error::Error for RecordError {}
+
+/// Log header.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct Header {
+    /// Calibration data of the device.
+    pub calibration: Calibration,
+}
+
+impl Header {
+    /// Length of a serialized header.
+    pub const LEN: usize = MAGIC.len() + 1 + Calibration::RECORD_LEN;
+
+    /// Create a new header.
+    pub fn new(calibration: Calibration) -> Self {
+        Self { calibration }
+    }
+
+    /// Serialize the header.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4] = VERSION;
+        bytes[5..].copy_from_slice(&self.calibration.to_record());
+
+        bytes
+    }
+
+    /// Deserialize a header created with [`Header::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; Self::LEN]) -> Result<Self, RecordError> {
+        if bytes[..4] != MAGIC {
+            return Err(RecordError::InvalidMagic);
+        }
+
+        if bytes[4] != VERSION {
+            return Err(RecordError::UnsupportedVersion(bytes[4]));
+        }
+
+        match Calibration::from_record(&bytes[5..]) {
+            Some(calibration) => Ok(Self { calibration }),
+            None => Err(RecordError::Crc),
+        }
+    }
+}
+
+/// Record flags.
+///
+/// The lower 7 bits carry the [`HealthIssue`]s of the sample, the upper bit marks failed compensations.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flags(u8);
+
+impl Flags {
+    /// No flags.
+    pub const NONE: Flags = Flags(0);
+
+    /// The compensation failed, the true temperature and pressure are invalid.
+    pub const COMPENSATION_FAILED: Flags = Flags(1 << 7);
+
+    /// Create flags from their bits.
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Flags of the given health status.
+    pub fn from_health(status: HealthStatus) -> Self {
+        Self(status.bits())
+    }
+
+    /// Bits of the flags.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Whether all flags of `other` are set.
+    pub fn contains(&self, other: Flags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether the given health issue is set.
+    pub fn has(&self, issue: HealthIssue) -> bool {
+        self.health().has(issue)
+    }
+
+    /// Health status carried by the flags.
+    pub fn health(&self) -> HealthStatus {
+        HealthStatus::from_bits(self.0 & !Self::COMPENSATION_FAILED.0)
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+
+    fn bitor(self, other: Flags) -> Flags {
+        Flags(self.0 | other.0)
+    }
+}
+
+/// Log record of a sample.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    /// Timestamp in a unit chosen by the logger, e.g. seconds since the Unix epoch.
+    pub timestamp: u32,
+    /// Mode used to measure the raw pressure.
+    pub mode: Mode,
+    /// Flags.
+    pub flags: Flags,
+    /// Raw temperature (`UT`).
+    pub raw_temperature: i16,
+    /// Raw pressure (`UP`).
+    pub raw_pressure: i32,
+    /// True temperature in `0.1 C`.
+    pub temperature: i16,
+    /// True pressure in `Pa`.
+    pub pressure: i32,
+}
+
+impl Record {
+    /// Length of a serialized record.
+    pub const LEN: usize = 20;
+
+    /// Serialize the record.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+
+        bytes[0..4].copy_from_slice(&self.timestamp.to_be_bytes());
+        bytes[4] = self.mode as u8;
+        bytes[5] = self.flags.bits();
+        bytes[6..8].copy_from_slice(&self.raw_temperature.to_be_bytes());
+        bytes[8..12].copy_from_slice(&self.raw_pressure.to_be_bytes());
+        bytes[12..14].copy_from_slice(&self.temperature.to_be_bytes());
+        bytes[14..18].copy_from_slice(&self.pressure.to_be_bytes());
+
+        let crc = crc16(&bytes[..18]);
+
+        bytes[18..].copy_from_slice(&crc.to_be_bytes());
+
+        bytes
+    }
+
+    /// Deserialize a record created with [`Record::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; Self::LEN]) -> Result<Self, RecordError> {
+        let crc = u16::from_be_bytes([bytes[18], bytes[19]]);
+
+        if crc16(&bytes[..18]) != crc {
+            return Err(RecordError::Crc);
+        }
+
+        let mode = match Mode::ALL.get(bytes[4] as usize) {
+            Some(mode) => *mode,
+            None => return Err(RecordError::InvalidMode(bytes[4])),
+        };
+
+        Ok(Self {
+            timestamp: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            mode,
+            flags: Flags::from_bits(bytes[5]),
+            raw_temperature: i16::from_be_bytes([bytes[6], bytes[7]]),
+            raw_pressure: i32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            temperature: i16::from_be_bytes([bytes[12], bytes[13]]),
+            pressure: i32::from_be_bytes([bytes[14], bytes[15], bytes[16], bytes[17]]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Calibration data of the datasheet example.
+    fn calibration() -> Calibration {
+        Calibration::from_slice(&[
+            0x01, 0x98, 0xff, 0xb8, 0xc7, 0xd1, 0x7f, 0xe5, 0x7f, 0xf5, 0x5a, 0x71, 0x18, 0x2e,
+            0x00, 0x04, 0x80, 0x00, 0xdd, 0xf9, 0x0b, 0x34,
+        ])
+    }
+
+    /// Record of the datasheet example.
+    const RECORD: Record = Record {
+        timestamp: 0x0102_0304,
+        mode: Mode::UltraLowPower,
+        flags: Flags::NONE,
+        raw_temperature: 27898,
+        raw_pressure: 23843,
+        temperature: 150,
+        pressure: 69964,
+    };
+
+    #[test]
+    fn header_round_trip() {
+        let bytes = Header::new(calibration()).to_bytes();
+
+        assert_eq!(bytes[..4], *b"BMPL");
+        assert_eq!(bytes[4], VERSION);
+        assert_eq!(bytes[5..], calibration().to_record());
+        assert_eq!(Header::from_bytes(&bytes), Ok(Header::new(calibration())));
+    }
+
+    #[test]
+    fn header_errors() {
+        let bytes = Header::new(calibration()).to_bytes();
+
+        let mut invalid = bytes;
+        invalid[0] = b'X';
+
+        assert_eq!(Header::from_bytes(&invalid), Err(RecordError::InvalidMagic));
+
+        let mut invalid = bytes;
+        invalid[4] = 1;
+
+        assert_eq!(
+            Header::from_bytes(&invalid),
+            Err(RecordError::UnsupportedVersion(1))
+        );
+
+        let mut invalid = bytes;
+        invalid[10] ^= 0x01;
+
+        assert_eq!(Header::from_bytes(&invalid), Err(RecordError::Crc));
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let record = Record {
+            mode: Mode::UltraHighResolution,
+            flags: Flags::from_bits(0b101) | Flags::COMPENSATION_FAILED,
+            raw_pressure: 190744,
+            temperature: -1,
+            pressure: i32::MAX,
+            ..RECORD
+        };
+
+        let bytes = record.to_bytes();
+
+        assert_eq!(bytes[..6], [0x01, 0x02, 0x03, 0x04, 3, 0b1000_0101]);
+        assert_eq!(bytes[6..8], 27898i16.to_be_bytes());
+        assert_eq!(Record::from_bytes(&bytes), Ok(record));
+        assert_eq!(Record::from_bytes(&RECORD.to_bytes()), Ok(RECORD));
+    }
+
+    #[test]
+    fn record_errors() {
+        let mut bytes = RECORD.to_bytes();
+
+        bytes[12] ^= 0x80;
+
+        assert_eq!(Record::from_bytes(&bytes), Err(RecordError::Crc));
+
+        // A valid CRC over an invalid mode.
+        let mut bytes = RECORD.to_bytes();
+
+        bytes[4] = 4;
+
+        let crc = crc16(&bytes[..18]).to_be_bytes();
+
+        bytes[18..].copy_from_slice(&crc);
+
+        assert_eq!(Record::from_bytes(&bytes), Err(RecordError::InvalidMode(4)));
+    }
+
+    #[test]
+    fn flags() {
+        let flags = Flags::from_bits(0b10) | Flags::COMPENSATION_FAILED;
+
+        assert!(flags.contains(Flags::COMPENSATION_FAILED));
+        assert!(flags.has(HealthIssue::TemperatureOutOfRange));
+        assert!(!flags.has(HealthIssue::PressureOutOfRange));
+        assert_eq!(flags.health(), HealthStatus::from_bits(0b10));
+        assert!(Flags::NONE.health().is_healthy());
+        assert_eq!(Flags::from_health(flags.health()).bits(), 0b10);
+    }
+}
@@ -0,0 +1,48 @@
+//! I2C bus recovery.
+//!
+//! A stuck bus, e.g. a slave holding `SDA` low after an interrupted transaction, does not recover on its own.
+//! Implement `BusRecovery` of the `asynch` or `blocking` module, e.g. by clocking `SCL` nine times
+//! followed by a stop condition, and set it using `UninitBMP180Builder::bus_recovery`.
+
+use duplicate::duplicate_item;
+
+/// Placeholder for an absent bus recovery.
+///
+/// Used as the default type of the optional bus recovery. Can not be constructed.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum NoRecovery {}
+
+#[duplicate_item(
+    feature_        module        async;
+    ["async"]       [asynch]      [async];
+    ["blocking"]    [blocking]    [];
+)]
+pub mod module {
+    //! I2C bus recovery.
+
+    #[cfg(feature=feature_)]
+    mod inner {
+        use super::super::NoRecovery;
+
+        /// I2C bus recovery.
+        ///
+        /// Invoked by the driver after repeated I2C failures, before the device ID is validated again.
+        /// A stuck bus is usually recovered by clocking `SCL` nine times followed by a stop condition.
+        #[allow(async_fn_in_trait)]
+        pub trait BusRecovery<I2C> {
+            /// Recover the I2C bus.
+            async fn recover(&mut self, i2c: &mut I2C);
+        }
+
+        impl<I2C> BusRecovery<I2C> for NoRecovery {
+            async fn recover(&mut self, _i2c: &mut I2C) {
+                match *self {}
+            }
+        }
+    }
+
+    #[cfg(feature=feature_)]
+    pub use inner::*;
+}
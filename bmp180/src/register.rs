@@ -2,13 +2,46 @@
 
 /// Register addresses for the `BMP180` device.
 #[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
 #[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
 pub enum Register {
+    /// Chip ID.
     ChipId = 0xD0,
+    /// Calibration word `AC1`, the start of the calibration data.
     CalibrationAc1 = 0xAA,
+    /// Calibration word `AC2`.
+    CalibrationAc2 = 0xAC,
+    /// Calibration word `AC3`.
+    CalibrationAc3 = 0xAE,
+    /// Calibration word `AC4`.
+    CalibrationAc4 = 0xB0,
+    /// Calibration word `AC5`.
+    CalibrationAc5 = 0xB2,
+    /// Calibration word `AC6`.
+    CalibrationAc6 = 0xB4,
+    /// Calibration word `B1`.
+    CalibrationB1 = 0xB6,
+    /// Calibration word `B2`.
+    CalibrationB2 = 0xB8,
+    /// Calibration word `MB`.
+    CalibrationMb = 0xBA,
+    /// Calibration word `MC`.
+    CalibrationMc = 0xBC,
+    /// Calibration word `MD`.
+    CalibrationMd = 0xBE,
+    /// Control register.
     Control = 0xF4,
+    /// Conversion result.
     TempPressureData = 0xF6,
+    /// Command starting a temperature conversion, written to [`Register::Control`].
     ReadTempCmd = 0x2E,
+    /// Command starting a pressure conversion in ultra low power mode, written to [`Register::Control`].
     ReadPressureCmd = 0x34,
 }
+
+/// Start of conversion (`sco`) bit of the [`Register::Control`] register.
+///
+/// Set while a conversion is running.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub const SCO: u8 = 1 << 5;
@@ -0,0 +1,306 @@
+//! Replay of recorded I2C traces.
+//!
+//! Plays back transactions recorded using [`Recorder`](crate::trace::Recorder), e.g. parsed from a field capture
+//! using [`trace::parse`](crate::trace::parse), to turn them into regression tests for `initialize` and `update`.
+//!
+//! ```
+//! # #[cfg(all(feature = "blocking", feature = "impl-debug"))]
+//! # {
+//! use bmp180_embedded_hal::{
+//!     blocking::UninitBMP180,
+//!     replay::{ReplayDelay, ReplayI2C},
+//!     trace, Mode,
+//! };
+//!
+//! // `initialize` and `update` of a device with the datasheet example values.
+//! const CAPTURE: &str = "\
+//! 0x77 W:d0 R:5500 OK
+//! 0x77 W:aa R:0198ffb8c7d17fe57ff55a71182e00048000ddf90b34 OK
+//! 0x77 W:f42e R: OK
+//! 0x77 W:f6 R:6cfa OK
+//! 0x77 W:f434 R: OK
+//! 0x77 W:f6 R:5d2300 OK
+//! ";
+//!
+//! let mut i2c = ReplayI2C::new(trace::parse(CAPTURE).map(Result::unwrap));
+//!
+//! let mut bmp180 = UninitBMP180::builder(&mut i2c, ReplayDelay)
+//!     .mode(Mode::UltraLowPower)
+//!     .build()
+//!     .initialize()
+//!     .unwrap();
+//!
+//! bmp180.update().unwrap();
+//!
+//! assert_eq!(bmp180.temperature(), 150);
+//! assert_eq!(bmp180.pressure(), 69964);
+//!
+//! i2c.finish();
+//! # }
+//! ```
+
+use duplicate::duplicate_item;
+use embedded_hal::i2c::{ErrorKind, Operation};
+
+use crate::{trace::Transaction, tri};
+
+/// Replay delay.
+///
+/// Does nothing.
+pub struct ReplayDelay;
+
+/// Replay I2C.
+///
+/// Responds to each transaction with the next recorded one.
+///
+/// # Panics
+///
+/// Panics if the driver issues more transactions than recorded,
+/// or a transaction differing from the recorded one in its address or written bytes.
+pub struct ReplayI2C<T> {
+    /// Recorded transactions.
+    transactions: T,
+    /// Number of replayed transactions.
+    replayed: usize,
+}
+
+impl<T> ReplayI2C<T>
+where
+    T: Iterator<Item = Transaction>,
+{
+    /// Create a new `ReplayI2C`.
+    pub fn new(transactions: impl IntoIterator<IntoIter = T>) -> Self {
+        Self {
+            transactions: transactions.into_iter(),
+            replayed: 0,
+        }
+    }
+
+    /// Number of replayed transactions.
+    pub fn replayed(&self) -> usize {
+        self.replayed
+    }
+
+    /// Assert that all recorded transactions were replayed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if recorded transactions are left.
+    pub fn finish(mut self) {
+        if let Some(transaction) = self.transactions.next() {
+            panic!(
+                "transaction {} was recorded but not replayed: {}",
+                self.replayed, transaction
+            );
+        }
+    }
+
+    /// Replay the next recorded transaction.
+    fn replay(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), ErrorKind> {
+        let index = self.replayed;
+
+        let Some(expected) = self.transactions.next() else {
+            panic!("transaction {index} was not recorded");
+        };
+
+        self.replayed += 1;
+
+        let mut actual = Transaction::new(address);
+
+        for operation in operations.iter() {
+            if let Operation::Write(bytes) = operation {
+                actual.push_write(bytes);
+            }
+        }
+
+        assert_eq!(
+            (actual.address, actual.write()),
+            (expected.address, expected.write()),
+            "transaction {index} differs from the recorded one"
+        );
+
+        tri!(expected.result);
+
+        let mut read = expected.read();
+
+        for operation in operations.iter_mut() {
+            if let Operation::Read(buffer) = operation {
+                assert!(
+                    buffer.len() <= read.len(),
+                    "transaction {index} reads more bytes than recorded"
+                );
+
+                let (bytes, rest) = read.split_at(buffer.len());
+
+                buffer.copy_from_slice(bytes);
+                read = rest;
+            }
+        }
+
+        assert!(
+            read.is_empty(),
+            "transaction {index} reads fewer bytes than recorded"
+        );
+
+        Ok(())
+    }
+}
+
+impl<T> embedded_hal::i2c::ErrorType for ReplayI2C<T> {
+    type Error = ErrorKind;
+}
+
+#[duplicate_item(
+    feature_        module        async     i2c_trait                       delay_trait;
+    ["async"]       [asynch]      [async]   [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs];
+    ["blocking"]    [blocking]    []        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs];
+)]
+mod module {
+    #[cfg(feature=feature_)]
+    mod inner {
+        use embedded_hal::i2c::Operation;
+
+        use crate::trace::Transaction;
+
+        use super::super::{ReplayDelay, ReplayI2C};
+
+        impl delay_trait for ReplayDelay {
+            async fn delay_ns(&mut self, _: u32) {}
+        }
+
+        impl<T> i2c_trait for ReplayI2C<T>
+        where
+            T: Iterator<Item = Transaction>,
+        {
+            async fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [Operation<'_>],
+            ) -> Result<(), Self::Error> {
+                self.replay(address, operations)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use embassy_futures::block_on;
+
+    use std::{string::String, vec::Vec};
+
+    use super::*;
+    use crate::{
+        asynch::UninitBMP180,
+        mock::mock,
+        trace::{self, Recorder, TraceBuffer},
+        Mode,
+    };
+
+    /// Record `initialize` and `update` of the simulated device in the text format.
+    ///
+    /// Returns the trace, the temperature and the pressure.
+    fn capture() -> (String, i32, i32) {
+        let (i2c, delay, _) = mock();
+
+        let mut buffer = TraceBuffer::<16>::new();
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(Recorder::new(i2c, &mut buffer), delay)
+                .mode(Mode::UltraLowPower)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        let (temperature, pressure) = (bmp180.temperature(), bmp180.pressure());
+
+        drop(bmp180);
+
+        let mut text = String::new();
+
+        buffer.dump(&mut text).unwrap();
+
+        (text, temperature, pressure)
+    }
+
+    /// Replay `initialize` and `update` from `text`.
+    ///
+    /// Returns the temperature and the pressure.
+    fn replay(text: &str) -> (i32, i32) {
+        let transactions: Vec<Transaction> = trace::parse(text).map(Result::unwrap).collect();
+
+        let mut i2c = ReplayI2C::new(transactions);
+
+        let mut bmp180 = block_on(
+            UninitBMP180::builder(&mut i2c, ReplayDelay)
+                .mode(Mode::UltraLowPower)
+                .build()
+                .initialize(),
+        )
+        .unwrap();
+
+        block_on(bmp180.update()).unwrap();
+
+        let readings = (bmp180.temperature(), bmp180.pressure());
+
+        assert_eq!(i2c.replayed(), 6);
+
+        i2c.finish();
+
+        readings
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let (text, temperature, pressure) = capture();
+
+        assert_eq!(replay(&text), (temperature, pressure));
+        assert_eq!((temperature, pressure), (150, 69964));
+    }
+
+    #[test]
+    #[should_panic(expected = "transaction 2 differs from the recorded one")]
+    fn diverging_write_panics() {
+        let (text, _, _) = capture();
+
+        // Start a pressure conversion where the driver starts a temperature conversion.
+        replay(&text.replacen("W:f42e", "W:f434", 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "transaction 0 differs from the recorded one")]
+    fn diverging_address_panics() {
+        let (text, _, _) = capture();
+
+        replay(&text.replace("0x77", "0x76"));
+    }
+
+    #[test]
+    #[should_panic(expected = "was not recorded")]
+    fn missing_transaction_panics() {
+        let (text, _, _) = capture();
+
+        let truncated: Vec<&str> = text.lines().take(4).collect();
+
+        replay(&truncated.join("\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "reads fewer bytes than recorded")]
+    fn diverging_read_panics() {
+        let (text, _, _) = capture();
+
+        replay(&text.replacen("W:d0 R:5500", "W:d0 R:550000", 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "transaction 6 was recorded but not replayed")]
+    fn leftover_transaction_panics() {
+        let (text, _, _) = capture();
+
+        replay(&std::format!("{text}0x77 W:d0 R:5500 OK\n"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Retry policy for transient I2C errors.
+
+use embedded_hal::i2c::ErrorKind;
+
+/// Delay between two attempts of a failed I2C transaction.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backoff {
+    /// Same delay in milliseconds before each retry.
+    Fixed(u32),
+    /// Delay in milliseconds doubling with each retry, starting at `initial_ms` and capped at `max_ms`.
+    Exponential {
+        /// Delay before the first retry.
+        initial_ms: u32,
+        /// Maximum delay.
+        max_ms: u32,
+    },
+}
+
+impl Backoff {
+    /// Delay in milliseconds before the given retry, starting at `1`.
+    fn delay_ms(&self, retry: u8) -> u32 {
+        match *self {
+            Backoff::Fixed(delay_ms) => delay_ms,
+            Backoff::Exponential { initial_ms, max_ms } => {
+                let factor = 1u32.checked_shl(retry.saturating_sub(1) as u32);
+
+                factor
+                    .and_then(|factor| initial_ms.checked_mul(factor))
+                    .unwrap_or(max_ms)
+                    .min(max_ms)
+            }
+        }
+    }
+}
+
+/// Kinds of I2C errors that are retried.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryOn {
+    /// Retry if the address or data was not acknowledged.
+    pub no_acknowledge: bool,
+    /// Retry if the arbitration was lost.
+    pub arbitration_loss: bool,
+    /// Retry on bus errors.
+    pub bus: bool,
+    /// Retry on overruns.
+    pub overrun: bool,
+    /// Retry on any other error.
+    pub other: bool,
+}
+
+impl RetryOn {
+    /// Whether the given error kind is retried.
+    pub fn matches(&self, kind: ErrorKind) -> bool {
+        match kind {
+            ErrorKind::NoAcknowledge(_) => self.no_acknowledge,
+            ErrorKind::ArbitrationLoss => self.arbitration_loss,
+            ErrorKind::Bus => self.bus,
+            ErrorKind::Overrun => self.overrun,
+            _ => self.other,
+        }
+    }
+}
+
+impl Default for RetryOn {
+    /// Retry on transient errors: missing acknowledges, arbitration losses, bus errors and overruns.
+    fn default() -> Self {
+        Self {
+            no_acknowledge: true,
+            arbitration_loss: true,
+            bus: true,
+            overrun: true,
+            other: false,
+        }
+    }
+}
+
+/// Retry policy applied to each I2C transaction.
+///
+/// The default policy does not retry.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+    /// Maximum number of attempts per transaction, including the first one.
+    max_attempts: u8,
+    /// Delay between two attempts.
+    backoff: Backoff,
+    /// Retried error kinds.
+    retry_on: RetryOn,
+}
+
+impl RetryPolicy {
+    /// Create a new policy with the given maximum number of attempts per transaction, including the first one.
+    ///
+    /// Retries immediately on transient errors, see [`RetryOn::default`].
+    pub fn new(max_attempts: u8) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff: Backoff::Fixed(0),
+            retry_on: RetryOn::default(),
+        }
+    }
+
+    /// Set the delay between two attempts.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the retried error kinds.
+    pub fn retry_on(mut self, retry_on: RetryOn) -> Self {
+        self.retry_on = retry_on;
+        self
+    }
+
+    /// Maximum number of attempts per transaction, including the first one.
+    pub fn max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+
+    /// Whether a transaction failing with `kind` after `attempt` attempts is retried.
+    pub(crate) fn should_retry(&self, attempt: u8, kind: ErrorKind) -> bool {
+        attempt < self.max_attempts && self.retry_on.matches(kind)
+    }
+
+    /// Delay in milliseconds before retrying after `attempt` attempts.
+    pub(crate) fn delay_ms(&self, attempt: u8) -> u32 {
+        self.backoff.delay_ms(attempt)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::NoAcknowledgeSource;
+
+    use super::*;
+
+    #[test]
+    fn fixed_backoff() {
+        let backoff = Backoff::Fixed(10);
+
+        assert_eq!(backoff.delay_ms(1), 10);
+        assert_eq!(backoff.delay_ms(u8::MAX), 10);
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let backoff = Backoff::Exponential {
+            initial_ms: 5,
+            max_ms: 100,
+        };
+
+        assert_eq!(backoff.delay_ms(1), 5);
+        assert_eq!(backoff.delay_ms(2), 10);
+        assert_eq!(backoff.delay_ms(3), 20);
+        assert_eq!(backoff.delay_ms(5), 80);
+        assert_eq!(backoff.delay_ms(6), 100);
+    }
+
+    #[test]
+    fn exponential_backoff_overflow() {
+        let backoff = Backoff::Exponential {
+            initial_ms: 5,
+            max_ms: u32::MAX,
+        };
+
+        // `5 << 30` overflows, `1 << 32` does not fit.
+        assert_eq!(backoff.delay_ms(31), u32::MAX);
+        assert_eq!(backoff.delay_ms(33), u32::MAX);
+        assert_eq!(backoff.delay_ms(u8::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn retry_on_matches() {
+        let retry_on = RetryOn::default();
+
+        assert!(retry_on.matches(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)));
+        assert!(retry_on.matches(ErrorKind::ArbitrationLoss));
+        assert!(retry_on.matches(ErrorKind::Bus));
+        assert!(retry_on.matches(ErrorKind::Overrun));
+        assert!(!retry_on.matches(ErrorKind::Other));
+
+        let retry_on = RetryOn {
+            no_acknowledge: false,
+            other: true,
+            ..retry_on
+        };
+
+        assert!(!retry_on.matches(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)));
+        assert!(retry_on.matches(ErrorKind::Other));
+    }
+
+    #[test]
+    fn policy_gives_up() {
+        let policy = RetryPolicy::new(3).retry_on(RetryOn {
+            bus: false,
+            ..RetryOn::default()
+        });
+
+        assert!(policy.should_retry(1, ErrorKind::ArbitrationLoss));
+        assert!(policy.should_retry(2, ErrorKind::ArbitrationLoss));
+        assert!(!policy.should_retry(3, ErrorKind::ArbitrationLoss));
+        assert!(!policy.should_retry(1, ErrorKind::Bus));
+        assert!(!policy.should_retry(1, ErrorKind::Other));
+
+        assert!(!RetryPolicy::default().should_retry(1, ErrorKind::ArbitrationLoss));
+    }
+}
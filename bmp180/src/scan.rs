@@ -0,0 +1,202 @@
+//! I2C bus scanning and discovery of `BMP180` devices.
+
+use duplicate::duplicate_item;
+use embedded_hal::i2c::ErrorKind;
+
+use crate::address::Address;
+
+/// Result of probing a single I2C address.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum Probe {
+    /// Address was not scanned.
+    #[default]
+    NotScanned,
+    /// A `BMP180` device answered with a valid device ID.
+    Found,
+    /// A device answered with an invalid device ID.
+    InvalidId(u8),
+    /// No device acknowledged the address.
+    NoAcknowledge,
+    /// Bus error other than a missing acknowledge.
+    BusError(ErrorKind),
+}
+
+/// Report of an I2C bus scan.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct ScanReport {
+    /// Probe results indexed by the 7-bit address.
+    probes: [Probe; 128],
+}
+
+impl ScanReport {
+    /// Create an empty report.
+    fn new() -> Self {
+        Self {
+            probes: [Probe::NotScanned; 128],
+        }
+    }
+
+    /// Probe result for the given 7-bit address.
+    pub fn probe(&self, address: u8) -> Probe {
+        self.probes
+            .get(address as usize)
+            .copied()
+            .unwrap_or(Probe::NotScanned)
+    }
+
+    /// Addresses answering with a valid `BMP180` device ID.
+    pub fn found(&self) -> impl Iterator<Item = Address> + '_ {
+        self.addresses_with(|probe| probe == Probe::Found)
+    }
+
+    /// Addresses that failed with a bus error other than a missing acknowledge.
+    pub fn bus_errors(&self) -> impl Iterator<Item = Address> + '_ {
+        self.addresses_with(|probe| matches!(probe, Probe::BusError(_)))
+    }
+
+    /// Addresses whose probe result matches `predicate`.
+    fn addresses_with<F>(&self, predicate: F) -> impl Iterator<Item = Address> + '_
+    where
+        F: Fn(Probe) -> bool + 'static,
+    {
+        self.probes
+            .iter()
+            .enumerate()
+            .filter(move |(_, probe)| predicate(**probe))
+            .map(|(address, _)| Address::from(address as u8))
+    }
+}
+
+#[duplicate_item(
+    feature_        module        async     await               i2c_trait;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c];
+)]
+pub mod module {
+    //! I2C bus scanning.
+
+    #[cfg(feature=feature_)]
+    mod inner {
+        use core::ops::RangeInclusive;
+
+        use embedded_hal::i2c::{Error, ErrorKind};
+
+        use crate::{device::Identity, id::Id, register::Register};
+
+        use super::super::{Probe, ScanReport};
+
+        /// Scan the given address range for `BMP180` devices.
+        ///
+        /// Every address is probed by reading the device ID register.
+        /// Addresses above `0x7F` are ignored. The usual range for 7-bit addresses is `0x08..=0x77`.
+        pub async fn scan<I2C>(i2c: &mut I2C, addresses: RangeInclusive<u8>) -> ScanReport
+        where
+            I2C: i2c_trait,
+        {
+            let mut report = ScanReport::new();
+
+            for address in addresses.filter(|address| *address <= 0x7F) {
+                let mut data = [0u8; 1];
+
+                let result = i2c
+                    .write_read(address, &[Register::ChipId as u8], &mut data)
+                    .await;
+
+                report.probes[address as usize] = match result {
+                    Ok(()) if Id::is_valid(data[0]) => Probe::Found,
+                    Ok(()) => Probe::InvalidId(data[0]),
+                    Err(error) => match error.kind() {
+                        ErrorKind::NoAcknowledge(_) => Probe::NoAcknowledge,
+                        kind => Probe::BusError(kind),
+                    },
+                };
+            }
+
+            report
+        }
+    }
+
+    #[cfg(feature=feature_)]
+    pub use inner::*;
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use embassy_futures::block_on;
+    use embedded_hal::i2c::{NoAcknowledgeSource, Operation};
+
+    use std::vec::Vec;
+
+    use super::{asynch::scan, *};
+
+    /// Bus with a valid device at `0x77`, an unknown device at `0x50` and a bus error at `0x60`.
+    #[derive(Default)]
+    struct Bus {
+        /// Probed addresses.
+        probed: Vec<u8>,
+    }
+
+    impl embedded_hal::i2c::ErrorType for Bus {
+        type Error = ErrorKind;
+    }
+
+    impl embedded_hal_async::i2c::I2c for Bus {
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.probed.push(address);
+
+            let id = match address {
+                0x77 => 0x55,
+                0x50 => 0x42,
+                0x60 => return Err(ErrorKind::ArbitrationLoss),
+                _ => return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            };
+
+            for operation in operations {
+                if let Operation::Read(buffer) = operation {
+                    buffer.fill(id);
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn scan_classifies_probes() {
+        let mut bus = Bus::default();
+
+        let report = block_on(scan(&mut bus, 0x08..=0x77));
+
+        assert_eq!(report.probe(0x77), Probe::Found);
+        assert_eq!(report.probe(0x50), Probe::InvalidId(0x42));
+        assert_eq!(
+            report.probe(0x60),
+            Probe::BusError(ErrorKind::ArbitrationLoss)
+        );
+        assert_eq!(report.probe(0x08), Probe::NoAcknowledge);
+        assert_eq!(report.probe(0x07), Probe::NotScanned);
+        assert_eq!(report.probe(0x78), Probe::NotScanned);
+
+        assert!(report.found().map(u8::from).eq([0x77]));
+        assert!(report.bus_errors().map(u8::from).eq([0x60]));
+    }
+
+    #[test]
+    fn scan_skips_addresses_above_7f() {
+        let mut bus = Bus::default();
+
+        let report = block_on(scan(&mut bus, 0x70..=0xFF));
+
+        assert_eq!(bus.probed, (0x70..=0x7F).collect::<Vec<_>>());
+        assert!(report.found().map(u8::from).eq([0x77]));
+        assert_eq!(report.probe(0x80), Probe::NotScanned);
+    }
+}
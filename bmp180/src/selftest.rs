@@ -0,0 +1,56 @@
+//! Self-test report.
+
+use crate::mode::Mode;
+
+/// Maximum spread in `Pa` of the pressures measured in the different modes.
+///
+/// Several times the RMS noise of the ultra low power mode (`6 Pa`).
+pub(crate) const MODE_PRESSURE_TOLERANCE: i32 = 100;
+
+/// Number of conversions used to check that the raw values change.
+pub(crate) const RAW_SAMPLES: usize = 8;
+
+/// Report of a `BMP180` self-test.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct SelfTestReport {
+    /// Device ID read from the device.
+    pub id: u8,
+    /// Whether the device ID is valid.
+    pub id_valid: bool,
+    /// Whether the calibration data is plausible, see [`Calibration::is_valid`](crate::Calibration::is_valid).
+    pub calibration_valid: bool,
+    /// Whether the raw temperature (`UT`) changed between consecutive conversions.
+    pub raw_temperature_changes: bool,
+    /// Whether the raw pressure (`UP`) changed between consecutive conversions.
+    pub raw_pressure_changes: bool,
+    /// Pressure in `Pa` measured in each mode, indexed by [`Mode`].
+    pub mode_pressures: [i32; 4],
+    /// Whether the pressures measured in the different modes are consistent.
+    pub modes_consistent: bool,
+    /// Whether the temperature conversion started and completed within the maximum conversion time.
+    pub temperature_timing: bool,
+    /// Whether the pressure conversion started and completed within the maximum conversion time, indexed by [`Mode`].
+    pub pressure_timing: [bool; 4],
+}
+
+impl SelfTestReport {
+    /// Whether every check passed.
+    ///
+    /// The raw temperature may legitimately stay the same in a stable environment,
+    /// so only one of the raw values is required to change.
+    pub fn passed(&self) -> bool {
+        self.id_valid
+            && self.calibration_valid
+            && (self.raw_temperature_changes || self.raw_pressure_changes)
+            && self.modes_consistent
+            && self.temperature_timing
+            && self.pressure_timing.iter().all(|timing| *timing)
+    }
+
+    /// Pressure in `Pa` measured in the given mode.
+    pub fn mode_pressure(&self, mode: Mode) -> i32 {
+        self.mode_pressures[mode as usize]
+    }
+}
@@ -0,0 +1,244 @@
+//! Sensor-agnostic barometer and thermometer traits.
+//!
+//! Application code generic over the `Barometer` and `Thermometer` traits of the `asynch` or `blocking` module
+//! does not need to name the concrete driver.
+//! Errors are classified using [`Error::kind`], similar to the `embedded-hal` error traits.
+
+use duplicate::duplicate_item;
+
+use crate::error::BMP180Error;
+
+/// Sensor error kind.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Error communicating with the sensor.
+    Bus,
+    /// The device is not the expected sensor.
+    InvalidDevice,
+    /// Error computing the compensated values.
+    Arithmetic,
+    /// Error of an additional pin of the sensor.
+    Pin,
+    /// Any other error.
+    Other,
+}
+
+/// Sensor error.
+pub trait Error {
+    /// Kind of the error.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl Error for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+}
+
+/// Sensor error type trait.
+pub trait ErrorType {
+    /// Error type.
+    type Error: Error;
+}
+
+impl<T: ErrorType + ?Sized> ErrorType for &mut T {
+    type Error = T::Error;
+}
+
+/// Sensor error wrapping a [`BMP180Error`].
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct SensorError<I2CError>(pub BMP180Error<I2CError>);
+
+impl<I2CError> SensorError<I2CError> {
+    /// Wrapped driver error.
+    pub fn into_inner(self) -> BMP180Error<I2CError> {
+        self.0
+    }
+}
+
+impl<I2CError> From<BMP180Error<I2CError>> for SensorError<I2CError> {
+    fn from(error: BMP180Error<I2CError>) -> Self {
+        Self(error)
+    }
+}
+
+impl<I2CError> Error for SensorError<I2CError> {
+    fn kind(&self) -> ErrorKind {
+        match self.0 {
+            BMP180Error::I2C { .. } => ErrorKind::Bus,
+            BMP180Error::InvalidId(_) => ErrorKind::InvalidDevice,
+            BMP180Error::Arithmetic(_) => ErrorKind::Arithmetic,
+            BMP180Error::Pin(_) => ErrorKind::Pin,
+        }
+    }
+}
+
+#[duplicate_item(
+    feature_        module        async     await               i2c_trait                       delay_trait                             eoc_trait;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c]  [embedded_hal_async::delay::DelayNs]    [embedded_hal_async::digital::Wait];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c]        [embedded_hal::delay::DelayNs]          [embedded_hal::digital::InputPin];
+)]
+pub mod module {
+    //! Barometer and thermometer traits.
+
+    #[cfg(feature=feature_)]
+    mod inner {
+        use embedded_hal::digital::OutputPin;
+
+        use crate::{
+            device::{module::BMP180, Identity},
+            recovery::module::BusRecovery,
+            tri,
+            units::{Celsius, Pascal},
+        };
+
+        use super::super::{ErrorType, SensorError};
+
+        /// Barometer.
+        #[allow(async_fn_in_trait)]
+        pub trait Barometer: ErrorType {
+            /// Measure the pressure.
+            async fn read_pressure(&mut self) -> Result<Pascal, Self::Error>;
+        }
+
+        /// Thermometer.
+        #[allow(async_fn_in_trait)]
+        pub trait Thermometer: ErrorType {
+            /// Measure the temperature.
+            async fn read_temperature(&mut self) -> Result<Celsius, Self::Error>;
+        }
+
+        impl<T: Barometer + ?Sized> Barometer for &mut T {
+            async fn read_pressure(&mut self) -> Result<Pascal, Self::Error> {
+                T::read_pressure(self).await
+            }
+        }
+
+        impl<T: Thermometer + ?Sized> Thermometer for &mut T {
+            async fn read_temperature(&mut self) -> Result<Celsius, Self::Error> {
+                T::read_temperature(self).await
+            }
+        }
+
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> ErrorType for BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
+            type Error = SensorError<I2C::Error>;
+        }
+
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> Barometer for BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
+            async fn read_pressure(&mut self) -> Result<Pascal, Self::Error> {
+                tri!(self.update_pressure().await.map_err(SensorError));
+
+                Ok(self.typed_pressure())
+            }
+        }
+
+        impl<I2C, DELAY, EOC, XCLR, RECOVERY> Thermometer for BMP180<I2C, DELAY, EOC, XCLR, RECOVERY>
+        where
+            I2C: i2c_trait,
+            DELAY: delay_trait,
+            EOC: eoc_trait,
+            XCLR: OutputPin,
+            RECOVERY: BusRecovery<I2C>,
+        {
+            async fn read_temperature(&mut self) -> Result<Celsius, Self::Error> {
+                tri!(self.update_temperature().await.map_err(SensorError));
+
+                Ok(Celsius::from(self.typed_temperature()))
+            }
+        }
+    }
+
+    #[cfg(feature=feature_)]
+    pub use inner::*;
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::{ErrorKind as I2CErrorKind, NoAcknowledgeSource};
+
+    use crate::error::{ArithmeticError, ArithmeticReason, Operation, Stage};
+
+    use super::*;
+
+    #[test]
+    fn error_kind() {
+        let kind = |error: BMP180Error<I2CErrorKind>| SensorError::from(error).kind();
+
+        assert_eq!(
+            kind(BMP180Error::I2C {
+                operation: Operation::ReadPressure,
+                error: I2CErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            }),
+            ErrorKind::Bus
+        );
+        assert_eq!(kind(BMP180Error::InvalidId(0x42)), ErrorKind::InvalidDevice);
+        assert_eq!(
+            kind(BMP180Error::Arithmetic(ArithmeticError {
+                stage: Stage::B5,
+                reason: ArithmeticReason::DivisionByZero,
+                raw_temperature: 20285,
+                raw_pressure: None,
+            })),
+            ErrorKind::Arithmetic
+        );
+        assert_eq!(
+            kind(BMP180Error::Pin(embedded_hal::digital::ErrorKind::Other)),
+            ErrorKind::Pin
+        );
+        assert_eq!(ErrorKind::Other.kind(), ErrorKind::Other);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn generic_sensor() {
+        use embassy_futures::block_on;
+
+        use crate::{
+            asynch::UninitBMP180,
+            mock::mock,
+            sensor::asynch::{Barometer, Thermometer},
+            units::{Celsius, Pascal},
+        };
+
+        /// Application code generic over the sensor.
+        async fn read<S: Barometer + Thermometer>(
+            mut sensor: S,
+        ) -> Result<(Celsius, Pascal), ErrorKind> {
+            let temperature = sensor.read_temperature().await.map_err(|e| e.kind())?;
+            let pressure = sensor.read_pressure().await.map_err(|e| e.kind())?;
+
+            Ok((temperature, pressure))
+        }
+
+        let (i2c, delay, state) = mock();
+
+        let mut bmp180 = block_on(UninitBMP180::builder(i2c, delay).build().initialize()).unwrap();
+
+        let (temperature, pressure) = block_on(read(&mut bmp180)).unwrap();
+
+        assert_eq!(temperature, Celsius(15.0));
+        assert_eq!(pressure, Pascal(bmp180.pressure()));
+
+        state.borrow_mut().failures = 1;
+
+        assert_eq!(block_on(read(&mut bmp180)), Err(ErrorKind::Bus));
+    }
+}
@@ -0,0 +1,164 @@
+//! JSON and postcard round-trip tests for the `serde` implementations.
+
+use core::fmt::Debug;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    health::{Limits, Reading},
+    record::{Flags, Record},
+    storm::Threshold,
+    units::{Celsius, DeciCelsius, Fahrenheit, Feet, HectoPascal, Meters, Pascal},
+    zambretti::{Hemisphere, Trend, WindDirection},
+    Address, Calibration, Mode,
+};
+
+/// Serialize `value` to JSON and postcard and assert that both deserialize to the same value.
+fn round_trip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_string(value).unwrap();
+
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+
+    let bytes = postcard::to_allocvec(value).unwrap();
+
+    assert_eq!(&postcard::from_bytes::<T>(&bytes).unwrap(), value);
+}
+
+/// Calibration data of the datasheet example.
+fn calibration() -> Calibration {
+    Calibration {
+        ac1: 408,
+        ac2: -72,
+        ac3: -14383,
+        ac4: 32741,
+        ac5: 32757,
+        ac6: 23153,
+        b1: 6190,
+        b2: 4,
+        mb: -32768,
+        mc: -8711,
+        md: 2868,
+    }
+}
+
+#[test]
+fn calibration_round_trip() {
+    round_trip(&calibration());
+    round_trip(&Calibration::default());
+}
+
+#[test]
+fn mode_round_trip() {
+    for mode in Mode::ALL {
+        round_trip(&mode);
+    }
+}
+
+#[test]
+fn address_round_trip() {
+    for address in [Address::Default, Address::Other(0x76)] {
+        let json = serde_json::to_string(&address).unwrap();
+        let decoded: Address = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(u8::from(decoded), u8::from(address));
+
+        let bytes = postcard::to_allocvec(&address).unwrap();
+        let decoded: Address = postcard::from_bytes(&bytes).unwrap();
+
+        assert_eq!(u8::from(decoded), u8::from(address));
+    }
+}
+
+#[test]
+fn units_round_trip() {
+    round_trip(&Pascal(101325));
+    round_trip(&HectoPascal(1013.25));
+    round_trip(&DeciCelsius(-125));
+    round_trip(&Celsius(-12.5));
+    round_trip(&Fahrenheit(9.5));
+    round_trip(&Meters(-42.5));
+    round_trip(&Feet(1234.5));
+}
+
+#[test]
+fn reading_round_trip() {
+    round_trip(&Reading {
+        temperature: 150,
+        pressure: 69964,
+        raw_temperature: 27898,
+        raw_pressure: 23843,
+    });
+}
+
+#[test]
+fn record_round_trip() {
+    round_trip(&Record {
+        timestamp: 1_700_000_000,
+        mode: Mode::UltraHighResolution,
+        flags: Flags::COMPENSATION_FAILED,
+        raw_temperature: 27898,
+        raw_pressure: 190744,
+        temperature: 150,
+        pressure: 69964,
+    });
+}
+
+#[test]
+fn settings_round_trip() {
+    round_trip(&Limits::default());
+
+    for trend in [Trend::Rising, Trend::Steady, Trend::Falling] {
+        round_trip(&trend);
+    }
+
+    for hemisphere in [Hemisphere::North, Hemisphere::South] {
+        round_trip(&hemisphere);
+    }
+
+    for wind in [WindDirection::N, WindDirection::SSW, WindDirection::NNW] {
+        round_trip(&wind);
+    }
+
+    let threshold = Threshold::FOUR_HPA_PER_THREE_HOURS;
+    let json = serde_json::to_string(&threshold).unwrap();
+    let decoded: Threshold = serde_json::from_str(&json).unwrap();
+
+    assert_eq!((decoded.drop, decoded.window), (400, 3 * 3600));
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+#[test]
+fn retry_settings_round_trip() {
+    use crate::{Backoff, RetryOn, RetryPolicy};
+
+    round_trip(&Backoff::Fixed(10));
+    round_trip(&Backoff::Exponential {
+        initial_ms: 1,
+        max_ms: 100,
+    });
+    round_trip(&RetryOn::default());
+    round_trip(&RetryPolicy::new(3).backoff(Backoff::Fixed(10)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn raw_sample_round_trip() {
+    use crate::rawlog::{Measurement, Sample};
+
+    let sample = Sample {
+        mode: Mode::UltraLowPower,
+        raw_temperature: 27898,
+        raw_pressure: 23843,
+    };
+
+    round_trip(&sample);
+    round_trip(&Measurement {
+        sample,
+        temperature: 150,
+        pressure: 69964,
+        altitude: 3015.5,
+    });
+}
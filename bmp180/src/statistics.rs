@@ -0,0 +1,191 @@
+//! Driver statistics.
+
+use embedded_hal::i2c::{Error, ErrorKind};
+
+use crate::error::{ArithmeticError, BMP180Error, Operation};
+
+/// Error recorded in the [`Statistics`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum RecordedError {
+    /// I2C error.
+    I2C {
+        /// Operation that failed.
+        operation: Operation,
+        /// Kind of the I2C error.
+        kind: ErrorKind,
+    },
+    /// Invalid device ID.
+    InvalidId(u8),
+    /// Arithmetic error.
+    Arithmetic(ArithmeticError),
+    /// `EOC` or `XCLR` pin error.
+    Pin(embedded_hal::digital::ErrorKind),
+}
+
+/// Bits clocked per byte, including the acknowledge bit.
+const BITS_PER_BYTE: u64 = 9;
+
+/// Address bytes sent after each start or repeated start condition.
+const ADDRESS_BYTES: usize = 1;
+
+/// Per-device statistics.
+///
+/// Include the transactions of the initialization and the errors of the device once initialized,
+/// a failed initialization returns its error instead since there is no device to record it in.
+///
+/// Only available when the `statistics` feature is enabled.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct Statistics {
+    /// Number of successful updates.
+    updates: u32,
+    /// Number of I2C errors indexed by [`Operation`].
+    i2c_errors: [u32; Operation::ALL.len()],
+    /// Number of arithmetic errors.
+    arithmetic_errors: u32,
+    /// Number of other errors.
+    other_errors: u32,
+    /// Number of I2C transactions, including retries.
+    transactions: u32,
+    /// Number of retried I2C transactions.
+    retries: u32,
+    /// Number of bits clocked on the I2C bus.
+    bus_bits: u64,
+    /// Last error.
+    last_error: Option<RecordedError>,
+}
+
+impl Statistics {
+    /// Number of successful updates.
+    pub fn updates(&self) -> u32 {
+        self.updates
+    }
+
+    /// Number of I2C errors while performing the given operation.
+    pub fn i2c_errors(&self, operation: Operation) -> u32 {
+        self.i2c_errors[operation as usize]
+    }
+
+    /// Number of I2C errors.
+    pub fn total_i2c_errors(&self) -> u32 {
+        self.i2c_errors
+            .iter()
+            .fold(0, |total, errors| total.saturating_add(*errors))
+    }
+
+    /// Number of arithmetic errors.
+    pub fn arithmetic_errors(&self) -> u32 {
+        self.arithmetic_errors
+    }
+
+    /// Number of invalid device ID and pin errors.
+    pub fn other_errors(&self) -> u32 {
+        self.other_errors
+    }
+
+    /// Number of I2C transactions, including retries.
+    pub fn transactions(&self) -> u32 {
+        self.transactions
+    }
+
+    /// Number of retried I2C transactions.
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Number of bits clocked on the I2C bus, including start, stop and acknowledge bits.
+    ///
+    /// Computed from the transferred bytes, not measured.
+    pub fn bus_bits(&self) -> u64 {
+        self.bus_bits
+    }
+
+    /// Estimated total bus time in microseconds at the given bus frequency.
+    ///
+    /// This is an estimate computed from [`bus_bits`](Self::bus_bits), not a measurement:
+    /// clock stretching and the time between transactions are not taken into account.
+    pub fn bus_time_us(&self, frequency_hz: u32) -> u64 {
+        self.bus_bits.saturating_mul(1_000_000) / frequency_hz.max(1) as u64
+    }
+
+    /// Last error.
+    pub fn last_error(&self) -> Option<RecordedError> {
+        self.last_error
+    }
+
+    /// Record a write of `written` bytes and its `retries` retries.
+    pub(crate) fn record_write(&mut self, written: usize, retries: u32) {
+        // Start, address, data and stop.
+        self.record_transaction(1, written, retries);
+    }
+
+    /// Record a write of `written` bytes followed by a read of `read` bytes and their `retries` retries.
+    pub(crate) fn record_write_read(&mut self, written: usize, read: usize, retries: u32) {
+        // Start, address, data, repeated start, address, data and stop.
+        self.record_transaction(2, written + read, retries);
+    }
+
+    /// Record an I2C transaction of `segments` segments, each started by a start or repeated start condition
+    /// and its address byte, carrying `bytes` data bytes in total, and its `retries` retries.
+    fn record_transaction(&mut self, segments: usize, bytes: usize, retries: u32) {
+        let attempts = retries.saturating_add(1);
+        let bytes = (segments * ADDRESS_BYTES + bytes) as u64;
+        // One condition per segment and the final stop condition.
+        let conditions = segments as u64 + 1;
+        let bits = (bytes * BITS_PER_BYTE + conditions) * attempts as u64;
+
+        self.transactions = self.transactions.saturating_add(attempts);
+        self.retries = self.retries.saturating_add(retries);
+        self.bus_bits = self.bus_bits.saturating_add(bits);
+    }
+
+    /// Record the result of an update.
+    pub(crate) fn record_update<E>(&mut self, result: &Result<(), BMP180Error<E>>)
+    where
+        E: Error,
+    {
+        match result {
+            Ok(()) => self.updates = self.updates.saturating_add(1),
+            Err(error) => self.record_error(error),
+        }
+    }
+
+    /// Record an error.
+    pub(crate) fn record_error<E>(&mut self, error: &BMP180Error<E>)
+    where
+        E: Error,
+    {
+        let recorded = match error {
+            BMP180Error::I2C { operation, error } => {
+                let errors = &mut self.i2c_errors[*operation as usize];
+
+                *errors = errors.saturating_add(1);
+
+                RecordedError::I2C {
+                    operation: *operation,
+                    kind: error.kind(),
+                }
+            }
+            BMP180Error::InvalidId(id) => {
+                self.other_errors = self.other_errors.saturating_add(1);
+
+                RecordedError::InvalidId(*id)
+            }
+            BMP180Error::Arithmetic(error) => {
+                self.arithmetic_errors = self.arithmetic_errors.saturating_add(1);
+
+                RecordedError::Arithmetic(*error)
+            }
+            BMP180Error::Pin(kind) => {
+                self.other_errors = self.other_errors.saturating_add(1);
+
+                RecordedError::Pin(*kind)
+            }
+        };
+
+        self.last_error = Some(recorded);
+    }
+}
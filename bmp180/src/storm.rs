@@ -0,0 +1,525 @@
+//! Rapid pressure drop storm alert detector.
+//!
+//! The detector is fed with successive pressure readings, e.g. `BMP180::pressure`
+//! after each `BMP180::update`, from either the `blocking` or the `asynch` device,
+//! and raises an event when the pressure drop over a time window exceeds one of the configured thresholds.
+
+use core::fmt;
+
+/// Pressure drop threshold.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Threshold {
+    /// Pressure drop in `Pa`.
+    pub drop: i32,
+    /// Time window in seconds.
+    pub window: u32,
+}
+
+impl Threshold {
+    /// Pressure drop of `2 hPa` in one hour.
+    pub const TWO_HPA_PER_HOUR: Threshold = Threshold::new(200, 3600);
+
+    /// Pressure drop of `4 hPa` in three hours.
+    pub const FOUR_HPA_PER_THREE_HOURS: Threshold = Threshold::new(400, 3 * 3600);
+
+    /// Create a new [`Threshold`] from a pressure drop in `Pa` and a time window in seconds.
+    pub const fn new(drop: i32, window: u32) -> Self {
+        Self { drop, window }
+    }
+}
+
+/// Error returned when the history of a [`StormDetector`] is too short for its thresholds.
+///
+/// Contains the number of samples required to cover the longest threshold window.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct HistoryTooShort(pub usize);
+
+impl fmt::Display for HistoryTooShort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "history too short, at least {} samples are required",
+            self.0
+        )
+    }
+}
+
+/// Only available when the `impl-debug` feature is enabled.
+#[cfg(feature = "impl-debug")]
+impl core::error::Error for HistoryTooShort {}
+
+/// Storm alert event.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum StormEvent {
+    /// Pressure drop exceeded a threshold.
+    Alert {
+        /// Index of the exceeded threshold.
+        threshold: usize,
+        /// Pressure drop in `Pa` over the threshold's time window.
+        drop: i32,
+    },
+    /// Pressure drop exceeded a more severe threshold while an alert was raised.
+    Escalated {
+        /// Index of the exceeded threshold.
+        threshold: usize,
+        /// Pressure drop in `Pa` over the threshold's time window.
+        drop: i32,
+    },
+    /// Pressure drop fell back below all thresholds minus the hysteresis.
+    Cleared,
+}
+
+/// Pressure sample.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+struct Sample {
+    /// Timestamp in seconds.
+    timestamp: u32,
+    /// Pressure in `Pa`.
+    pressure: i32,
+}
+
+/// Rapid pressure drop storm alert detector.
+///
+/// - `N`: number of samples kept in history.
+///   The history must cover the longest threshold window for the drop to be computed over the whole window.
+/// - `T`: number of thresholds, ordered by increasing severity.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct StormDetector<const N: usize, const T: usize> {
+    /// Pressure drop thresholds.
+    thresholds: [Threshold; T],
+    /// Hysteresis in `Pa` to apply before clearing an alert.
+    hysteresis: i32,
+    /// Number of consecutive samples required to raise or clear an alert.
+    debounce: u8,
+    /// Ring buffer of samples.
+    samples: [Sample; N],
+    /// Number of valid samples.
+    len: usize,
+    /// Index of the next sample to write.
+    head: usize,
+    /// Index of the most severe exceeded threshold while an alert is raised.
+    level: Option<usize>,
+    /// State change the pending samples are in favor of.
+    candidate: Option<Transition>,
+    /// Number of consecutive samples in favor of the candidate state change.
+    pending: u8,
+}
+
+/// State change of a [`StormDetector`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+enum Transition {
+    /// Raise an alert.
+    Alert,
+    /// Raise the alert level.
+    Escalate,
+    /// Clear the alert.
+    Clear,
+}
+
+impl<const N: usize, const T: usize> StormDetector<N, T> {
+    /// Create a new detector with the given thresholds, no hysteresis and no de-bouncing.
+    ///
+    /// - `thresholds`: pressure drop thresholds, ordered by increasing severity.
+    /// - `interval`: interval between two readings in seconds.
+    ///
+    /// Returns an error if `N` samples taken every `interval` seconds do not cover the longest threshold window.
+    pub fn new(thresholds: [Threshold; T], interval: u32) -> Result<Self, HistoryTooShort> {
+        let window = thresholds
+            .iter()
+            .map(|threshold| threshold.window)
+            .max()
+            .unwrap_or(0);
+
+        let required = (window / interval.max(1)) as usize + 1;
+
+        if N < required {
+            return Err(HistoryTooShort(required));
+        }
+
+        Ok(Self {
+            thresholds,
+            hysteresis: 0,
+            debounce: 1,
+            samples: [Sample::default(); N],
+            len: 0,
+            head: 0,
+            level: None,
+            candidate: None,
+            pending: 0,
+        })
+    }
+
+    /// Set the hysteresis in `Pa`.
+    ///
+    /// A raised alert is only cleared once the drop is below every threshold minus the hysteresis.
+    pub fn hysteresis(mut self, hysteresis: i32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Set the number of consecutive samples required to raise or clear an alert.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn debounce(mut self, samples: u8) -> Self {
+        self.debounce = samples.max(1);
+        self
+    }
+
+    /// Whether an alert is currently raised.
+    pub fn is_alerting(&self) -> bool {
+        self.level.is_some()
+    }
+
+    /// Index of the most severe exceeded threshold while an alert is raised.
+    pub fn level(&self) -> Option<usize> {
+        self.level
+    }
+
+    /// Clear the history and the alert state.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.head = 0;
+        self.level = None;
+        self.pending = 0;
+    }
+
+    /// Pressure drop in `Pa` over the given time window, ending at `timestamp`.
+    ///
+    /// The drop is the difference between the highest pressure in the window and the latest one.
+    fn drop_over(&self, window: u32, timestamp: u32, pressure: i32) -> i32 {
+        let max = self.samples[..self.len]
+            .iter()
+            .filter(|sample| timestamp.wrapping_sub(sample.timestamp) <= window)
+            .map(|sample| sample.pressure)
+            .max()
+            .unwrap_or(pressure);
+
+        max.saturating_sub(pressure)
+    }
+
+    /// Most severe exceeded threshold and its pressure drop in `Pa`.
+    fn exceeded(&self, timestamp: u32, pressure: i32) -> Option<(usize, i32)> {
+        self.thresholds
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, threshold)| {
+                (
+                    index,
+                    threshold,
+                    self.drop_over(threshold.window, timestamp, pressure),
+                )
+            })
+            .find(|(_, threshold, drop)| *drop >= threshold.drop)
+            .map(|(index, _, drop)| (index, drop))
+    }
+
+    /// Count a sample in favor of the given state change.
+    ///
+    /// The count restarts if the previous sample was in favor of another state change.
+    /// Returns whether enough consecutive samples were counted.
+    fn debounced(&mut self, transition: Transition) -> bool {
+        if self.candidate != Some(transition) {
+            self.candidate = Some(transition);
+            self.pending = 0;
+        }
+
+        self.pending = self.pending.saturating_add(1);
+
+        if self.pending < self.debounce {
+            return false;
+        }
+
+        self.pending = 0;
+
+        true
+    }
+
+    /// Add a new pressure reading.
+    ///
+    /// - `timestamp`: monotonic timestamp in seconds.
+    /// - `pressure`: pressure in `Pa`.
+    ///
+    /// Returns an event if the alert state changed or a more severe threshold was exceeded.
+    pub fn update(&mut self, timestamp: u32, pressure: i32) -> Option<StormEvent> {
+        if N == 0 {
+            return None;
+        }
+
+        self.samples[self.head] = Sample {
+            timestamp,
+            pressure,
+        };
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        if let Some(level) = self.level {
+            let cleared = self.thresholds.iter().all(|threshold| {
+                self.drop_over(threshold.window, timestamp, pressure)
+                    < threshold.drop.saturating_sub(self.hysteresis)
+            });
+
+            if cleared {
+                if !self.debounced(Transition::Clear) {
+                    return None;
+                }
+
+                self.level = None;
+
+                return Some(StormEvent::Cleared);
+            }
+
+            let Some((threshold, drop)) = self
+                .exceeded(timestamp, pressure)
+                .filter(|(threshold, _)| *threshold > level)
+            else {
+                self.pending = 0;
+
+                return None;
+            };
+
+            if !self.debounced(Transition::Escalate) {
+                return None;
+            }
+
+            self.level = Some(threshold);
+
+            return Some(StormEvent::Escalated { threshold, drop });
+        }
+
+        let Some((threshold, drop)) = self.exceeded(timestamp, pressure) else {
+            self.pending = 0;
+
+            return None;
+        };
+
+        if !self.debounced(Transition::Alert) {
+            return None;
+        }
+
+        self.level = Some(threshold);
+
+        Some(StormEvent::Alert { threshold, drop })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Interval between two readings in seconds.
+    const INTERVAL: u32 = 600;
+
+    /// Detector for `2 hPa` in one hour and `4 hPa` in three hours with readings every ten minutes.
+    fn detector() -> StormDetector<19, 2> {
+        StormDetector::new(
+            [
+                Threshold::TWO_HPA_PER_HOUR,
+                Threshold::FOUR_HPA_PER_THREE_HOURS,
+            ],
+            INTERVAL,
+        )
+        .unwrap()
+    }
+
+    /// Feed the detector with a pressure series, starting at `start` seconds.
+    ///
+    /// Returns the events with the index of the reading causing them.
+    fn feed<const N: usize, const T: usize>(
+        detector: &mut StormDetector<N, T>,
+        start: u32,
+        series: &[i32],
+    ) -> std::vec::Vec<(usize, StormEvent)> {
+        series
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pressure)| {
+                detector
+                    .update(start + index as u32 * INTERVAL, *pressure)
+                    .map(|event| (index, event))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn history_too_short() {
+        let thresholds = [
+            Threshold::TWO_HPA_PER_HOUR,
+            Threshold::FOUR_HPA_PER_THREE_HOURS,
+        ];
+
+        assert_eq!(
+            StormDetector::<18, 2>::new(thresholds, INTERVAL).err(),
+            Some(HistoryTooShort(19))
+        );
+        assert!(StormDetector::<19, 2>::new(thresholds, INTERVAL).is_ok());
+    }
+
+    #[test]
+    fn steady_pressure() {
+        let mut detector = detector();
+
+        assert!(feed(&mut detector, 0, &[101325; 30]).is_empty());
+        assert!(!detector.is_alerting());
+    }
+
+    #[test]
+    fn onset_escalation_and_clear() {
+        let mut detector = detector();
+
+        // Steady, then falling by 50 Pa every ten minutes, then steady again.
+        let series: [i32; 60] =
+            core::array::from_fn(|index| 101325 - 50 * (index.clamp(5, 29) as i32 - 5));
+
+        let events = feed(&mut detector, 0, &series);
+
+        assert_eq!(
+            events,
+            [
+                // 200 Pa over the last hour.
+                (
+                    9,
+                    StormEvent::Alert {
+                        threshold: 0,
+                        drop: 200
+                    }
+                ),
+                // 400 Pa over the last three hours.
+                (
+                    13,
+                    StormEvent::Escalated {
+                        threshold: 1,
+                        drop: 400
+                    }
+                ),
+                // The drop over three hours is below 400 Pa again.
+                (40, StormEvent::Cleared),
+            ]
+        );
+        assert!(!detector.is_alerting());
+    }
+
+    #[test]
+    fn onset_at_most_severe_threshold() {
+        let mut detector = detector();
+
+        // Sudden drop of 500 Pa.
+        let events = feed(&mut detector, 0, &[101325, 101325, 100825]);
+
+        assert_eq!(
+            events,
+            [(
+                2,
+                StormEvent::Alert {
+                    threshold: 1,
+                    drop: 500
+                }
+            )]
+        );
+        assert_eq!(detector.level(), Some(1));
+    }
+
+    #[test]
+    fn hysteresis_delays_clear() {
+        // Sudden drop of 250 Pa, then a recovery of 100 Pa.
+        let series = [
+            101325, 101325, 101075, 101175, 101175, 101175, 101175, 101175, 101175,
+        ];
+
+        let detector = StormDetector::<7, 1>::new([Threshold::TWO_HPA_PER_HOUR], INTERVAL).unwrap();
+
+        let alert = (
+            2,
+            StormEvent::Alert {
+                threshold: 0,
+                drop: 250,
+            },
+        );
+
+        // The drop of 150 Pa is below the threshold.
+        let events = feed(&mut detector.clone(), 0, &series);
+
+        assert_eq!(events, [alert, (3, StormEvent::Cleared)]);
+
+        // The drop of 150 Pa is not below the threshold minus the hysteresis,
+        // the alert is cleared once the peak left the window.
+        let events = feed(&mut detector.hysteresis(100), 0, &series);
+
+        assert_eq!(events, [alert, (8, StormEvent::Cleared)]);
+    }
+
+    #[test]
+    fn debounce() {
+        let mut detector = detector().debounce(3);
+
+        // Single outlier, then a sustained drop.
+        let events = feed(
+            &mut detector,
+            0,
+            &[101325, 101025, 101325, 101025, 101025, 101025],
+        );
+
+        assert_eq!(
+            events,
+            [(
+                5,
+                StormEvent::Alert {
+                    threshold: 0,
+                    drop: 300
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn alternating_transitions_are_debounced() {
+        let mut detector = detector().debounce(2);
+
+        // A sustained drop of 250 Pa, then samples alternating between
+        // a full recovery (clear) and a drop of 500 Pa (escalate).
+        let events = feed(
+            &mut detector,
+            0,
+            &[
+                101325, 101075, 101075, 101325, 100825, 101325, 100825, 101325, 100825,
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [(
+                2,
+                StormEvent::Alert {
+                    threshold: 0,
+                    drop: 250
+                }
+            )]
+        );
+        assert_eq!(detector.level(), Some(0));
+    }
+
+    #[test]
+    fn reset_clears_alert() {
+        let mut detector = detector();
+
+        assert!(feed(&mut detector, 0, &[101325, 101025]).len() == 1);
+
+        detector.reset();
+
+        assert!(!detector.is_alerting());
+        assert!(feed(&mut detector, 0, &[101025; 5]).is_empty());
+    }
+}
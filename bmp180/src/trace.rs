@@ -0,0 +1,622 @@
+//! I2C transaction recording.
+//!
+//! Wrap any I2C bus in a [`Recorder`] and pass it to `UninitBMP180::builder` to record every transaction
+//! into a [`TraceBuffer`] or any other [`TraceSink`]. The trace can be dumped in a text format,
+//! one transaction per line:
+//!
+//! ```text
+//! 0x77 W:f6 R:5fa0 OK
+//! 0x77 W:f434 R: ERR:nack-address
+//! ```
+//!
+//! Each line contains the address, the written and read bytes in hex and the result of the transaction.
+//! Dumped traces are parsed back using [`parse`].
+
+use core::{fmt, str::FromStr};
+
+use duplicate::duplicate_item;
+use embedded_hal::i2c::{Error, ErrorKind, NoAcknowledgeSource, Operation};
+
+use crate::tri;
+
+/// Maximum number of written or read bytes recorded per transaction.
+///
+/// The longest transaction of the driver, reading the calibration data, reads `22` bytes.
+pub const MAX_BYTES: usize = 32;
+
+/// Recorded I2C transaction.
+///
+/// Written and read bytes of all operations of a transaction are concatenated.
+/// Bytes beyond [`MAX_BYTES`] are not recorded.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct Transaction {
+    /// I2C address.
+    pub address: u8,
+    /// Written bytes.
+    write: [u8; MAX_BYTES],
+    /// Number of written bytes.
+    write_len: u8,
+    /// Read bytes.
+    read: [u8; MAX_BYTES],
+    /// Number of read bytes.
+    read_len: u8,
+    /// Result of the transaction.
+    pub result: Result<(), ErrorKind>,
+}
+
+impl Transaction {
+    /// Create a new successful transaction without any bytes.
+    pub const fn new(address: u8) -> Self {
+        Self {
+            address,
+            write: [0; MAX_BYTES],
+            write_len: 0,
+            read: [0; MAX_BYTES],
+            read_len: 0,
+            result: Ok(()),
+        }
+    }
+
+    /// Written bytes.
+    pub fn write(&self) -> &[u8] {
+        &self.write[..self.write_len as usize]
+    }
+
+    /// Read bytes.
+    ///
+    /// Empty if the transaction failed.
+    pub fn read(&self) -> &[u8] {
+        &self.read[..self.read_len as usize]
+    }
+
+    /// Append written bytes.
+    pub fn push_write(&mut self, bytes: &[u8]) {
+        Self::push(&mut self.write, &mut self.write_len, bytes);
+    }
+
+    /// Append read bytes.
+    pub fn push_read(&mut self, bytes: &[u8]) {
+        Self::push(&mut self.read, &mut self.read_len, bytes);
+    }
+
+    /// Append bytes to `buffer`, dropping the bytes that do not fit.
+    fn push(buffer: &mut [u8; MAX_BYTES], len: &mut u8, bytes: &[u8]) {
+        let start = *len as usize;
+        let count = bytes.len().min(MAX_BYTES - start);
+
+        buffer[start..start + count].copy_from_slice(&bytes[..count]);
+
+        *len += count as u8;
+    }
+}
+
+/// Name of an I2C error kind in the text format.
+fn error_kind_name(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => "nack-address",
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => "nack-data",
+        ErrorKind::NoAcknowledge(_) => "nack",
+        ErrorKind::ArbitrationLoss => "arbitration-loss",
+        ErrorKind::Bus => "bus",
+        ErrorKind::Overrun => "overrun",
+        _ => "other",
+    }
+}
+
+/// I2C error kind of a name in the text format.
+fn error_kind_from_name(name: &str) -> Option<ErrorKind> {
+    let kind = match name {
+        "nack-address" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        "nack-data" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        "nack" => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+        "arbitration-loss" => ErrorKind::ArbitrationLoss,
+        "bus" => ErrorKind::Bus,
+        "overrun" => ErrorKind::Overrun,
+        "other" => ErrorKind::Other,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+/// Field of a transaction in the text format that could not be parsed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub enum ParseErrorKind {
+    /// Missing or invalid address.
+    Address,
+    /// Missing or invalid written bytes, or more than [`MAX_BYTES`].
+    Write,
+    /// Missing or invalid read bytes, or more than [`MAX_BYTES`].
+    Read,
+    /// Missing or invalid result, or trailing fields.
+    Result,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self {
+            ParseErrorKind::Address => "invalid address",
+            ParseErrorKind::Write => "invalid written bytes",
+            ParseErrorKind::Read => "invalid read bytes",
+            ParseErrorKind::Result => "invalid result",
+        };
+
+        f.write_str(field)
+    }
+}
+
+/// Error parsing a trace in the text format.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct ParseError {
+    /// Line number, starting at `1`.
+    pub line: usize,
+    /// Field that could not be parsed.
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on line {}", self.kind, self.line)
+    }
+}
+
+/// Parse hex bytes using `push`, failing with `kind`.
+#[allow(clippy::manual_is_multiple_of)] // `is_multiple_of` requires a newer MSRV.
+fn parse_bytes(
+    hex: &str,
+    kind: ParseErrorKind,
+    mut push: impl FnMut(&[u8]),
+) -> Result<(), ParseErrorKind> {
+    if hex.len() % 2 != 0 || hex.len() > MAX_BYTES * 2 {
+        return Err(kind);
+    }
+
+    for i in (0..hex.len()).step_by(2) {
+        let byte = tri!(hex
+            .get(i..i + 2)
+            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            .ok_or(kind));
+
+        push(&[byte]);
+    }
+
+    Ok(())
+}
+
+impl FromStr for Transaction {
+    type Err = ParseErrorKind;
+
+    /// Parse a transaction from a line of the text format.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.split_whitespace();
+
+        let address = tri!(fields
+            .next()
+            .and_then(|address| address.strip_prefix("0x"))
+            .and_then(|address| u8::from_str_radix(address, 16).ok())
+            .ok_or(ParseErrorKind::Address));
+
+        let mut transaction = Transaction::new(address);
+
+        let write = tri!(fields
+            .next()
+            .and_then(|write| write.strip_prefix("W:"))
+            .ok_or(ParseErrorKind::Write));
+
+        tri!(parse_bytes(write, ParseErrorKind::Write, |bytes| {
+            transaction.push_write(bytes)
+        }));
+
+        let read = tri!(fields
+            .next()
+            .and_then(|read| read.strip_prefix("R:"))
+            .ok_or(ParseErrorKind::Read));
+
+        tri!(parse_bytes(read, ParseErrorKind::Read, |bytes| {
+            transaction.push_read(bytes)
+        }));
+
+        transaction.result = match fields.next() {
+            Some("OK") => Ok(()),
+            Some(result) => Err(tri!(result
+                .strip_prefix("ERR:")
+                .and_then(error_kind_from_name)
+                .ok_or(ParseErrorKind::Result))),
+            None => return Err(ParseErrorKind::Result),
+        };
+
+        if fields.next().is_some() {
+            return Err(ParseErrorKind::Result);
+        }
+
+        Ok(transaction)
+    }
+}
+
+/// Parse a trace in the text format, one transaction per line.
+///
+/// Empty lines are skipped.
+pub fn parse(text: &str) -> Parse<'_> {
+    Parse {
+        lines: text.lines().enumerate(),
+    }
+}
+
+/// Iterator over the transactions of a trace in the text format, see [`parse`].
+#[derive(Clone)]
+pub struct Parse<'text> {
+    /// Numbered lines, starting at `0`.
+    lines: core::iter::Enumerate<core::str::Lines<'text>>,
+}
+
+impl Iterator for Parse<'_> {
+    type Item = Result<Transaction, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, line) = self.lines.find(|(_, line)| !line.trim().is_empty())?;
+
+        Some(line.parse().map_err(|kind| ParseError {
+            line: index + 1,
+            kind,
+        }))
+    }
+}
+
+impl fmt::Display for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#04x} W:", self.address)?;
+
+        for byte in self.write() {
+            write!(f, "{byte:02x}")?;
+        }
+
+        f.write_str(" R:")?;
+
+        for byte in self.read() {
+            write!(f, "{byte:02x}")?;
+        }
+
+        match self.result {
+            Ok(()) => f.write_str(" OK"),
+            Err(kind) => write!(f, " ERR:{}", error_kind_name(kind)),
+        }
+    }
+}
+
+/// Destination of recorded transactions.
+pub trait TraceSink {
+    /// Record a transaction.
+    fn record(&mut self, transaction: &Transaction);
+}
+
+impl<T: TraceSink + ?Sized> TraceSink for &mut T {
+    fn record(&mut self, transaction: &Transaction) {
+        T::record(self, transaction)
+    }
+}
+
+/// Fixed-capacity buffer of the last `N` recorded transactions.
+///
+/// Overwrites the oldest transaction when full.
+#[derive(Clone)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct TraceBuffer<const N: usize> {
+    /// Transactions, in a ring.
+    transactions: [Transaction; N],
+    /// Index of the oldest transaction.
+    start: usize,
+    /// Number of transactions.
+    len: usize,
+    /// Number of overwritten transactions.
+    overwritten: u32,
+}
+
+impl<const N: usize> TraceBuffer<N> {
+    /// Create an empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            transactions: [Transaction::new(0); N],
+            start: 0,
+            len: 0,
+            overwritten: 0,
+        }
+    }
+
+    /// Number of transactions in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of transactions overwritten because the buffer was full.
+    pub fn overwritten(&self) -> u32 {
+        self.overwritten
+    }
+
+    /// Remove all transactions.
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+        self.overwritten = 0;
+    }
+
+    /// Transactions, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> + '_ {
+        (0..self.len).map(move |i| &self.transactions[(self.start + i) % N])
+    }
+
+    /// Dump the transactions in the text format, one per line.
+    pub fn dump<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        for transaction in self.iter() {
+            writeln!(writer, "{transaction}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for TraceBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> TraceSink for TraceBuffer<N> {
+    fn record(&mut self, transaction: &Transaction) {
+        if N == 0 {
+            self.overwritten = self.overwritten.saturating_add(1);
+
+            return;
+        }
+
+        if self.len == N {
+            self.transactions[self.start] = *transaction;
+            self.start = (self.start + 1) % N;
+            self.overwritten = self.overwritten.saturating_add(1);
+
+            return;
+        }
+
+        self.transactions[(self.start + self.len) % N] = *transaction;
+        self.len += 1;
+    }
+}
+
+/// I2C bus recording every transaction into a [`TraceSink`].
+pub struct Recorder<I2C, SINK> {
+    /// Recorded I2C bus.
+    i2c: I2C,
+    /// Destination of the recorded transactions.
+    sink: SINK,
+}
+
+impl<I2C, SINK> Recorder<I2C, SINK> {
+    /// Create a new recorder.
+    pub fn new(i2c: I2C, sink: SINK) -> Self {
+        Self { i2c, sink }
+    }
+
+    /// Destination of the recorded transactions.
+    pub fn sink(&self) -> &SINK {
+        &self.sink
+    }
+
+    /// Mutable destination of the recorded transactions.
+    pub fn sink_mut(&mut self) -> &mut SINK {
+        &mut self.sink
+    }
+
+    /// Release the I2C bus and the sink.
+    pub fn release(self) -> (I2C, SINK) {
+        (self.i2c, self.sink)
+    }
+}
+
+impl<I2C, SINK> Recorder<I2C, SINK>
+where
+    I2C: embedded_hal::i2c::ErrorType,
+    SINK: TraceSink,
+{
+    /// Record a finished transaction.
+    fn record(
+        &mut self,
+        address: u8,
+        operations: &[Operation<'_>],
+        result: &Result<(), I2C::Error>,
+    ) {
+        let mut transaction = Transaction::new(address);
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => transaction.push_write(bytes),
+                Operation::Read(buffer) if result.is_ok() => transaction.push_read(buffer),
+                Operation::Read(_) => {}
+            }
+        }
+
+        transaction.result = result.as_ref().map(|_| ()).map_err(|error| error.kind());
+
+        self.sink.record(&transaction);
+    }
+}
+
+impl<I2C, SINK> embedded_hal::i2c::ErrorType for Recorder<I2C, SINK>
+where
+    I2C: embedded_hal::i2c::ErrorType,
+{
+    type Error = I2C::Error;
+}
+
+#[duplicate_item(
+    feature_        module        async     await               i2c_trait;
+    ["async"]       [asynch]      [async]   [await.identity()]  [embedded_hal_async::i2c::I2c];
+    ["blocking"]    [blocking]    []        [identity()]        [embedded_hal::i2c::I2c];
+)]
+mod module {
+    #[cfg(feature=feature_)]
+    mod inner {
+        use embedded_hal::i2c::Operation;
+
+        use crate::device::Identity;
+
+        use super::super::{Recorder, TraceSink};
+
+        impl<I2C, SINK> i2c_trait for Recorder<I2C, SINK>
+        where
+            I2C: i2c_trait,
+            SINK: TraceSink,
+        {
+            async fn transaction(
+                &mut self,
+                address: u8,
+                operations: &mut [Operation<'_>],
+            ) -> Result<(), Self::Error> {
+                let result = self.i2c.transaction(address, operations).await;
+
+                self.record(address, operations, &result);
+
+                result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{string::String, vec::Vec};
+
+    use super::*;
+
+    /// Transaction writing `write`, reading `read` with the given result.
+    fn transaction(write: &[u8], read: &[u8], result: Result<(), ErrorKind>) -> Transaction {
+        let mut transaction = Transaction::new(0x77);
+
+        transaction.push_write(write);
+        transaction.push_read(read);
+        transaction.result = result;
+
+        transaction
+    }
+
+    /// Parse `text`, failing on the first error.
+    fn parse_all(text: &str) -> Result<Vec<Transaction>, ParseError> {
+        parse(text).collect()
+    }
+
+    #[test]
+    fn dump_parse_round_trip() {
+        let mut buffer = TraceBuffer::<4>::new();
+
+        let transactions = [
+            transaction(&[0xd0], &[0x55], Ok(())),
+            transaction(&[0xaa], &[0xa5; MAX_BYTES], Ok(())),
+            transaction(
+                &[0xf4, 0x2e],
+                &[],
+                Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            ),
+            transaction(&[], &[], Err(ErrorKind::ArbitrationLoss)),
+        ];
+
+        for transaction in &transactions {
+            buffer.record(transaction);
+        }
+
+        let mut text = String::new();
+
+        buffer.dump(&mut text).unwrap();
+
+        assert_eq!(text.lines().next(), Some("0x77 W:d0 R:55 OK"));
+        assert_eq!(text.lines().nth(2), Some("0x77 W:f42e R: ERR:nack-address"));
+        assert_eq!(parse_all(&text).unwrap(), transactions);
+    }
+
+    #[test]
+    fn parse_skips_empty_lines() {
+        let transactions = parse_all("\n0x77 W:d0 R:55 OK\n\n  \n0x76 W: R: ERR:bus\n").unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[1].address, 0x76);
+        assert_eq!(transactions[1].result, Err(ErrorKind::Bus));
+    }
+
+    #[test]
+    fn parse_malformed_lines() {
+        let error = |line: &str| parse_all(line).unwrap_err().kind;
+
+        assert_eq!(error("77 W:d0 R:55 OK"), ParseErrorKind::Address);
+        assert_eq!(error("0x777 W:d0 R:55 OK"), ParseErrorKind::Address);
+        assert_eq!(error("0x77 W:d R:55 OK"), ParseErrorKind::Write);
+        assert_eq!(error("0x77 W:dx R:55 OK"), ParseErrorKind::Write);
+        assert_eq!(error("0x77 d0 R:55 OK"), ParseErrorKind::Write);
+        assert_eq!(error("0x77 W:d0 R:555 OK"), ParseErrorKind::Read);
+        assert_eq!(error("0x77 W:d0"), ParseErrorKind::Read);
+        assert_eq!(error("0x77 W:d0 R:55"), ParseErrorKind::Result);
+        assert_eq!(error("0x77 W:d0 R:55 ERR:unknown"), ParseErrorKind::Result);
+        assert_eq!(error("0x77 W:d0 R:55 OK OK"), ParseErrorKind::Result);
+    }
+
+    #[test]
+    fn parse_too_many_bytes() {
+        let bytes = "ab".repeat(MAX_BYTES);
+        let too_many = "ab".repeat(MAX_BYTES + 1);
+
+        let line = std::format!("0x77 W:{bytes} R:{bytes} OK");
+        let transaction = parse_all(&line).unwrap()[0];
+
+        assert_eq!(transaction.write(), [0xab; MAX_BYTES]);
+        assert_eq!(transaction.read(), [0xab; MAX_BYTES]);
+
+        let line = std::format!("0x77 W:{too_many} R: OK");
+
+        assert_eq!(parse_all(&line).unwrap_err().kind, ParseErrorKind::Write);
+
+        let line = std::format!("0x77 W:d0 R:{too_many} OK");
+
+        assert_eq!(parse_all(&line).unwrap_err().kind, ParseErrorKind::Read);
+    }
+
+    #[test]
+    fn parse_error_line_number() {
+        let error = parse_all("0x77 W:d0 R:55 OK\n\n0x77 W:d0 R:5 OK\n").unwrap_err();
+
+        assert_eq!(
+            error,
+            ParseError {
+                line: 3,
+                kind: ParseErrorKind::Read,
+            }
+        );
+        assert_eq!(std::format!("{error}"), "invalid read bytes on line 3");
+    }
+
+    #[test]
+    fn buffer_keeps_last_transactions() {
+        let mut buffer = TraceBuffer::<2>::new();
+
+        for byte in 0..3 {
+            buffer.record(&transaction(&[byte], &[], Ok(())));
+        }
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.overwritten(), 1);
+        assert_eq!(
+            buffer
+                .iter()
+                .map(|transaction| transaction.write()[0])
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Lightweight physical unit newtypes.
+//!
+//! Conversions between units of the same quantity are provided via [`From`].
+//! Conversions from floating point to integer units round to the nearest integer.
+
+use core::fmt;
+
+/// Pressure in `Pa`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pascal(pub i32);
+
+/// Pressure in `hPa`.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HectoPascal(pub f32);
+
+/// Temperature in `0.1 C`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeciCelsius(pub i32);
+
+/// Temperature in `C`.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Celsius(pub f32);
+
+/// Temperature in `F`.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fahrenheit(pub f32);
+
+/// Length in `m`.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meters(pub f32);
+
+/// Length in `ft`.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Feet(pub f32);
+
+/// Meters per foot.
+const METERS_PER_FOOT: f32 = 0.3048;
+
+impl From<Pascal> for HectoPascal {
+    fn from(pressure: Pascal) -> HectoPascal {
+        HectoPascal(pressure.0 as f32 / 100.0)
+    }
+}
+
+impl From<HectoPascal> for Pascal {
+    /// Rounds to the nearest `Pa`.
+    fn from(pressure: HectoPascal) -> Pascal {
+        Pascal(libm::roundf(pressure.0 * 100.0) as i32)
+    }
+}
+
+impl From<DeciCelsius> for Celsius {
+    fn from(temperature: DeciCelsius) -> Celsius {
+        Celsius(temperature.0 as f32 / 10.0)
+    }
+}
+
+impl From<Celsius> for DeciCelsius {
+    /// Rounds to the nearest `0.1 C`.
+    fn from(temperature: Celsius) -> DeciCelsius {
+        DeciCelsius(libm::roundf(temperature.0 * 10.0) as i32)
+    }
+}
+
+impl From<Celsius> for Fahrenheit {
+    fn from(temperature: Celsius) -> Fahrenheit {
+        Fahrenheit(temperature.0 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+impl From<Fahrenheit> for Celsius {
+    fn from(temperature: Fahrenheit) -> Celsius {
+        Celsius((temperature.0 - 32.0) * 5.0 / 9.0)
+    }
+}
+
+impl From<DeciCelsius> for Fahrenheit {
+    fn from(temperature: DeciCelsius) -> Fahrenheit {
+        Celsius::from(temperature).into()
+    }
+}
+
+impl From<Fahrenheit> for DeciCelsius {
+    /// Rounds to the nearest `0.1 C`.
+    fn from(temperature: Fahrenheit) -> DeciCelsius {
+        Celsius::from(temperature).into()
+    }
+}
+
+impl From<Meters> for Feet {
+    fn from(length: Meters) -> Feet {
+        Feet(length.0 / METERS_PER_FOOT)
+    }
+}
+
+impl From<Feet> for Meters {
+    fn from(length: Feet) -> Meters {
+        Meters(length.0 * METERS_PER_FOOT)
+    }
+}
+
+impl fmt::Display for Pascal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" Pa")
+    }
+}
+
+impl fmt::Display for HectoPascal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" hPa")
+    }
+}
+
+impl fmt::Display for DeciCelsius {
+    /// Formats the temperature in `C` with exactly one decimal, e.g. `-0.5 °C`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+
+        write!(f, "{}{}.{} °C", sign, abs / 10, abs % 10)
+    }
+}
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" °C")
+    }
+}
+
+impl fmt::Display for Fahrenheit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" °F")
+    }
+}
+
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" m")
+    }
+}
+
+impl fmt::Display for Feet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" ft")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn pressure_conversions() {
+        assert_eq!(HectoPascal::from(Pascal(101325)), HectoPascal(1013.25));
+        assert_eq!(Pascal::from(HectoPascal(1013.254)), Pascal(101325));
+        assert_eq!(Pascal::from(HectoPascal(1013.256)), Pascal(101326));
+        assert_eq!(Pascal::from(HectoPascal(-0.5)), Pascal(-50));
+    }
+
+    #[test]
+    fn temperature_conversions() {
+        assert_eq!(Celsius::from(DeciCelsius(150)), Celsius(15.0));
+        assert_eq!(Celsius::from(DeciCelsius(-5)), Celsius(-0.5));
+        assert_eq!(DeciCelsius::from(Celsius(21.46)), DeciCelsius(215));
+        assert_eq!(DeciCelsius::from(Celsius(-0.54)), DeciCelsius(-5));
+        assert_eq!(Fahrenheit::from(Celsius(100.0)), Fahrenheit(212.0));
+        assert_eq!(Fahrenheit::from(Celsius(-40.0)), Fahrenheit(-40.0));
+        assert_eq!(Celsius::from(Fahrenheit(32.0)), Celsius(0.0));
+        assert_eq!(Fahrenheit::from(DeciCelsius(-400)), Fahrenheit(-40.0));
+        assert_eq!(DeciCelsius::from(Fahrenheit(0.0)), DeciCelsius(-178));
+    }
+
+    #[test]
+    fn length_conversions() {
+        assert!((Meters::from(Feet(1000.0)).0 - 304.8).abs() < 1e-3);
+        assert!((Feet::from(Meters(-304.8)).0 + 1000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Pascal(101325).to_string(), "101325 Pa");
+        assert_eq!(HectoPascal(1013.25).to_string(), "1013.25 hPa");
+        assert_eq!(DeciCelsius(150).to_string(), "15.0 °C");
+        assert_eq!(DeciCelsius(-5).to_string(), "-0.5 °C");
+        assert_eq!(DeciCelsius(-123).to_string(), "-12.3 °C");
+        assert_eq!(DeciCelsius(i32::MIN).to_string(), "-214748364.8 °C");
+        assert_eq!(Celsius(-0.5).to_string(), "-0.5 °C");
+        assert_eq!(Fahrenheit(59.0).to_string(), "59 °F");
+        assert_eq!(Meters(-12.5).to_string(), "-12.5 m");
+        assert_eq!(Feet(100.0).to_string(), "100 ft");
+        assert_eq!(std::format!("{:.1}", Celsius(15.04)), "15.0 °C");
+    }
+}
@@ -0,0 +1,464 @@
+//! Zambretti short-term weather forecaster.
+//!
+//! Implementation of the table based Zambretti algorithm as published by
+//! [beteljuice](http://www.beteljuice.co.uk/zambretti/forecast.html).
+//! The forecast is computed from the sea level pressure (see `BMP180::sea_level_pressure`),
+//! its trend, the month, the hemisphere and optionally the wind direction.
+
+/// Upper bound of the barometer range in `hPa`.
+const BARO_TOP: f32 = 1050.0;
+
+/// Lower bound of the barometer range in `hPa`.
+const BARO_BOTTOM: f32 = 950.0;
+
+/// Number of options the barometer range is divided into.
+const OPTIONS: usize = 22;
+
+/// Forecast texts indexed by the forecast letter (`A` to `Z`).
+const FORECASTS: [&str; 26] = [
+    "Settled fine",
+    "Fine weather",
+    "Becoming fine",
+    "Fine, becoming less settled",
+    "Fine, possible showers",
+    "Fairly fine, improving",
+    "Fairly fine, possible showers early",
+    "Fairly fine, showery later",
+    "Showery early, improving",
+    "Changeable, mending",
+    "Fairly fine, showers likely",
+    "Rather unsettled clearing later",
+    "Unsettled, probably improving",
+    "Showery, bright intervals",
+    "Showery, becoming less settled",
+    "Changeable, some rain",
+    "Unsettled, short fine intervals",
+    "Unsettled, rain later",
+    "Unsettled, some rain",
+    "Mostly very unsettled",
+    "Occasional rain, worsening",
+    "Rain at times, very unsettled",
+    "Rain at frequent intervals",
+    "Rain, very unsettled",
+    "Stormy, may improve",
+    "Stormy, much rain",
+];
+
+/// Forecast indices for a rising pressure, from the lowest to the highest option.
+const RISING_OPTIONS: [u8; OPTIONS] = [
+    25, 25, 25, 24, 24, 19, 16, 12, 11, 9, 8, 6, 5, 2, 1, 1, 0, 0, 0, 0, 0, 0,
+];
+
+/// Forecast indices for a steady pressure, from the lowest to the highest option.
+const STEADY_OPTIONS: [u8; OPTIONS] = [
+    25, 25, 25, 25, 25, 25, 23, 23, 22, 18, 15, 13, 10, 4, 1, 1, 0, 0, 0, 0, 0, 0,
+];
+
+/// Forecast indices for a falling pressure, from the lowest to the highest option.
+const FALLING_OPTIONS: [u8; OPTIONS] = [
+    25, 25, 25, 25, 25, 25, 25, 25, 23, 23, 21, 20, 17, 14, 7, 3, 1, 1, 1, 0, 0, 0,
+];
+
+/// Pressure trend.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trend {
+    /// Pressure is rising.
+    Rising,
+    /// Pressure is steady.
+    Steady,
+    /// Pressure is falling.
+    Falling,
+}
+
+/// Hemisphere of the observation.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hemisphere {
+    /// Northern hemisphere.
+    #[default]
+    North,
+    /// Southern hemisphere.
+    South,
+}
+
+/// Wind direction, 16 point compass rose.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindDirection {
+    /// North.
+    N = 0,
+    /// North-northeast.
+    NNE = 1,
+    /// Northeast.
+    NE = 2,
+    /// East-northeast.
+    ENE = 3,
+    /// East.
+    E = 4,
+    /// East-southeast.
+    ESE = 5,
+    /// Southeast.
+    SE = 6,
+    /// South-southeast.
+    SSE = 7,
+    /// South.
+    S = 8,
+    /// South-southwest.
+    SSW = 9,
+    /// Southwest.
+    SW = 10,
+    /// West-southwest.
+    WSW = 11,
+    /// West.
+    W = 12,
+    /// West-northwest.
+    WNW = 13,
+    /// Northwest.
+    NW = 14,
+    /// North-northwest.
+    NNW = 15,
+}
+
+impl WindDirection {
+    /// Pressure adjustment in percent of the barometer range for the northern hemisphere.
+    ///
+    /// Values for the southern hemisphere are the same, with the compass rose rotated by 180 degrees.
+    fn adjustment_percent(&self, hemisphere: Hemisphere) -> f32 {
+        const ADJUSTMENTS: [f32; 16] = [
+            6.0, 5.0, 5.0, 2.0, -0.5, -2.0, -5.0, -8.5, -12.0, -10.0, -6.0, -4.5, -3.0, -0.5, 1.5,
+            3.0,
+        ];
+
+        let index = match hemisphere {
+            Hemisphere::North => *self as usize,
+            Hemisphere::South => (*self as usize + 8) % 16,
+        };
+
+        ADJUSTMENTS[index]
+    }
+}
+
+/// Zambretti forecast.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "impl-defmt-format", derive(defmt::Format))]
+#[cfg_attr(feature = "impl-debug", derive(core::fmt::Debug))]
+pub struct Forecast {
+    /// Index of the forecast in the Zambretti table.
+    index: u8,
+    /// Pressure was outside of the `950 hPa` to `1050 hPa` range.
+    exceptional: bool,
+}
+
+impl Forecast {
+    /// Zambretti forecast letter, `A` to `Z`.
+    pub fn letter(&self) -> char {
+        (b'A' + self.index) as char
+    }
+
+    /// Zambretti forecast text.
+    pub fn text(&self) -> &'static str {
+        FORECASTS[self.index as usize]
+    }
+
+    /// Whether the pressure was outside of the `950 hPa` to `1050 hPa` range.
+    ///
+    /// The published algorithm prefixes the forecast text with `Exceptional Weather, ` in that case.
+    pub fn is_exceptional(&self) -> bool {
+        self.exceptional
+    }
+}
+
+/// Compute the Zambretti forecast.
+///
+/// - `sea_level_pressure`: pressure at sea level in `Pa`.
+/// - `trend`: pressure trend, usually over the last three hours.
+/// - `month`: month of the year, `1` (January) to `12` (December).
+/// - `hemisphere`: hemisphere of the observation.
+/// - `wind`: wind direction, if known.
+///
+/// Returns `None` if `month` is not in `1..=12`.
+pub fn forecast(
+    sea_level_pressure: i32,
+    trend: Trend,
+    month: u8,
+    hemisphere: Hemisphere,
+    wind: Option<WindDirection>,
+) -> Option<Forecast> {
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+
+    let range = BARO_TOP - BARO_BOTTOM;
+    let constant = range / OPTIONS as f32;
+
+    let mut pressure = sea_level_pressure as f32 / 100.0;
+
+    if let Some(wind) = wind {
+        pressure += wind.adjustment_percent(hemisphere) / 100.0 * range;
+    }
+
+    let summer = (4..=9).contains(&month);
+    let adjust_for_season = match hemisphere {
+        Hemisphere::North => summer,
+        Hemisphere::South => !summer,
+    };
+
+    if adjust_for_season {
+        match trend {
+            Trend::Rising => pressure += 7.0 / 100.0 * range,
+            Trend::Falling => pressure -= 7.0 / 100.0 * range,
+            Trend::Steady => {}
+        }
+    }
+
+    if pressure == BARO_TOP {
+        pressure = BARO_TOP - 1.0;
+    }
+
+    let option = libm::floorf((pressure - BARO_BOTTOM) / constant);
+
+    let (option, exceptional) = if option < 0.0 {
+        (0, true)
+    } else if option > (OPTIONS - 1) as f32 {
+        (OPTIONS - 1, true)
+    } else {
+        (option as usize, false)
+    };
+
+    let index = match trend {
+        Trend::Rising => RISING_OPTIONS[option],
+        Trend::Steady => STEADY_OPTIONS[option],
+        Trend::Falling => FALLING_OPTIONS[option],
+    };
+
+    Some(Forecast { index, exceptional })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sea level pressure, trend, month, hemisphere, wind and the expected forecast letter and exceptional flag.
+    type Case = (
+        i32,
+        Trend,
+        u8,
+        Hemisphere,
+        Option<WindDirection>,
+        char,
+        bool,
+    );
+
+    /// Expected forecasts, computed by hand from the published tables.
+    const CASES: [Case; 20] = [
+        // Winter in the northern hemisphere, no seasonal adjustment.
+        (
+            101325,
+            Trend::Rising,
+            1,
+            Hemisphere::North,
+            None,
+            'C',
+            false,
+        ),
+        (
+            101325,
+            Trend::Steady,
+            1,
+            Hemisphere::North,
+            None,
+            'E',
+            false,
+        ),
+        (
+            101325,
+            Trend::Falling,
+            1,
+            Hemisphere::North,
+            None,
+            'O',
+            false,
+        ),
+        // Summer in the northern hemisphere, rising and falling trends are adjusted by 7%.
+        (
+            101325,
+            Trend::Rising,
+            7,
+            Hemisphere::North,
+            None,
+            'B',
+            false,
+        ),
+        (
+            101325,
+            Trend::Steady,
+            7,
+            Hemisphere::North,
+            None,
+            'E',
+            false,
+        ),
+        (
+            101325,
+            Trend::Falling,
+            7,
+            Hemisphere::North,
+            None,
+            'R',
+            false,
+        ),
+        // Seasons are swapped in the southern hemisphere.
+        (
+            101325,
+            Trend::Rising,
+            1,
+            Hemisphere::South,
+            None,
+            'B',
+            false,
+        ),
+        (
+            101325,
+            Trend::Steady,
+            1,
+            Hemisphere::South,
+            None,
+            'E',
+            false,
+        ),
+        (
+            101325,
+            Trend::Falling,
+            1,
+            Hemisphere::South,
+            None,
+            'R',
+            false,
+        ),
+        (
+            101325,
+            Trend::Rising,
+            7,
+            Hemisphere::South,
+            None,
+            'C',
+            false,
+        ),
+        (
+            101325,
+            Trend::Steady,
+            7,
+            Hemisphere::South,
+            None,
+            'E',
+            false,
+        ),
+        (
+            101325,
+            Trend::Falling,
+            7,
+            Hemisphere::South,
+            None,
+            'O',
+            false,
+        ),
+        // Summer is April to September.
+        (
+            101325,
+            Trend::Rising,
+            3,
+            Hemisphere::North,
+            None,
+            'C',
+            false,
+        ),
+        (
+            101325,
+            Trend::Rising,
+            4,
+            Hemisphere::North,
+            None,
+            'B',
+            false,
+        ),
+        (
+            101325,
+            Trend::Rising,
+            9,
+            Hemisphere::North,
+            None,
+            'B',
+            false,
+        ),
+        (
+            101325,
+            Trend::Rising,
+            10,
+            Hemisphere::North,
+            None,
+            'C',
+            false,
+        ),
+        // Wind adjustment, the compass rose is rotated in the southern hemisphere.
+        (
+            100100,
+            Trend::Steady,
+            1,
+            Hemisphere::North,
+            Some(WindDirection::N),
+            'K',
+            false,
+        ),
+        (
+            100100,
+            Trend::Steady,
+            1,
+            Hemisphere::South,
+            Some(WindDirection::S),
+            'K',
+            false,
+        ),
+        // Pressure outside of the barometer range.
+        (94000, Trend::Steady, 1, Hemisphere::North, None, 'Z', true),
+        (106000, Trend::Steady, 1, Hemisphere::North, None, 'A', true),
+    ];
+
+    #[test]
+    fn forecast_matches_tables() {
+        for (pressure, trend, month, hemisphere, wind, letter, exceptional) in CASES {
+            let forecast = forecast(pressure, trend, month, hemisphere, wind).unwrap();
+
+            assert_eq!(
+                (forecast.letter(), forecast.is_exceptional()),
+                (letter, exceptional),
+                "{pressure} Pa, {trend:?}, month {month}, {hemisphere:?}, {wind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn forecast_without_wind() {
+        let forecast = forecast(100100, Trend::Steady, 1, Hemisphere::North, None).unwrap();
+
+        assert_eq!(forecast.letter(), 'N');
+        assert_eq!(forecast.text(), "Showery, bright intervals");
+    }
+
+    #[test]
+    fn invalid_month() {
+        for month in [0, 13, u8::MAX] {
+            assert_eq!(
+                forecast(101325, Trend::Steady, month, Hemisphere::North, None),
+                None
+            );
+        }
+    }
+}